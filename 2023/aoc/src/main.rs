@@ -0,0 +1,120 @@
+use anyhow::{anyhow, bail, Context};
+use std::io::Read;
+
+use common::{Answer, ParseError, Part, Solution};
+
+const USAGE: &str = "usage: aoc run <year> <day> [--part <1|2>] [--input <path>]";
+
+struct Args {
+    year: usize,
+    day: usize,
+    part: Option<Part>,
+    input: Option<String>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> anyhow::Result<Args> {
+    match args.next().as_deref() {
+        Some("run") => {}
+        Some(cmd) => bail!("Unknown command {cmd:?}\n{USAGE}"),
+        None => bail!("{USAGE}"),
+    }
+
+    let year = args
+        .next()
+        .ok_or(anyhow!("Expected a year\n{USAGE}"))?
+        .parse()
+        .context("Invalid year")?;
+    let day = args
+        .next()
+        .ok_or(anyhow!("Expected a day\n{USAGE}"))?
+        .parse()
+        .context("Invalid day")?;
+
+    let mut part = None;
+    let mut input = None;
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(anyhow!("Missing value for {arg}"));
+        match arg.as_str() {
+            "--part" => part = Some(value()?.parse()?),
+            "--input" => input = Some(value()?),
+            a => bail!("Unexpected argument {a:?}\n{USAGE}"),
+        }
+    }
+
+    Ok(Args {
+        year,
+        day,
+        part,
+        input,
+    })
+}
+
+fn solve_2023(day: usize, input: &str, part: Part) -> anyhow::Result<Answer> {
+    match day {
+        1 => day1::Day1::solve(input, part),
+        2 => day2::Day2::solve(input, part),
+        3 => day3::Day3::solve(input, part),
+        4 => day4::Day4::solve(input, part),
+        5 => day5::Day5::solve(input, part),
+        6 => day6::Day6::solve(input, part),
+        7 => day7::Day7::solve(input, part),
+        8 => day8::Day8::solve(input, part),
+        9 => day9::Day9::solve(input, part),
+        10 => day10::Day10::solve(input, part),
+        11 => day11::Day11::solve(input, part),
+        12 => day12::Day12::solve(input, part),
+        13 => day13::Day13::solve(input, part),
+        14 => day14::Day14::solve(input, part),
+        15 => day15::Day15::solve(input, part),
+        16 => day16::Day16::solve(input, part),
+        17 => day17::Day17::solve(input, part),
+        18 => day18::Day18::solve(input, part),
+        19 => day19::Day19::solve(input, part),
+        20 => day20::Day20::solve(input, part),
+        21 => day21::Day21::solve(input, part),
+        22 => day22::Day22::solve(input, part),
+        23 => day23::Day23::solve(input, part),
+        24 => day24::Day24::solve(input, part),
+        d => bail!("No solution for 2023 day {d}"),
+    }
+}
+
+fn solve(year: usize, day: usize, input: &str, part: Part) -> anyhow::Result<Answer> {
+    match year {
+        2023 => solve_2023(day, input, part),
+        y => bail!("No solutions registered for year {y}"),
+    }
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = parse_args(std::env::args().skip(1))?;
+
+    let input = match &args.input {
+        Some(path) => {
+            std::fs::read_to_string(path).with_context(|| format!("Unable to read {path}"))?
+        }
+        None => {
+            let mut text = String::new();
+            std::io::stdin().lock().read_to_string(&mut text)?;
+            text
+        }
+    };
+
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => vec![Part::One, Part::Two],
+    };
+
+    for part in parts {
+        let answer = solve(args.year, args.day, &input, part).map_err(|e| {
+            match (e.downcast::<ParseError>(), &args.input) {
+                (Ok(e), Some(path)) => e.with_file(path).into(),
+                (Ok(e), None) => e.into(),
+                (Err(e), _) => e,
+            }
+        })?;
+        println!("{} day {} part {part}: {answer}", args.year, args.day);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,317 @@
+use crate::{parse, ParseError};
+
+/// Row and column of a cell.
+pub type Pos = (usize, usize);
+
+const NEIGHBOURS4: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+const NEIGHBOURS8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// Rectangular grid stored in a single row-major vector.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(rows: usize, cols: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: vec![value; rows * cols],
+            rows,
+            cols,
+        }
+    }
+
+    pub fn from_vec(rows: usize, cols: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), rows * cols, "Grid of {rows}x{cols} cells");
+        Self { cells, rows, cols }
+    }
+
+    /// Parses one cell per character. Every line must have the same number of characters.
+    pub fn parse_with(
+        input: &str,
+        mut cell: impl FnMut(&str) -> Result<T, ParseError>,
+    ) -> Result<Self, ParseError> {
+        let mut cells = vec![];
+        let mut rows = 0;
+        let mut cols = None;
+        for line in input.lines() {
+            let len = line.chars().count();
+            match cols {
+                None => cols = Some(len),
+                Some(c) if c != len => {
+                    return Err(ParseError::new(line, format!("a row of {c} tiles")));
+                }
+                _ => {}
+            }
+
+            for (i, c) in line.char_indices() {
+                cells.push(cell(&line[i..i + c.len_utf8()])?);
+            }
+            rows += 1;
+        }
+
+        Ok(Self::from_vec(rows, cols.unwrap_or(0), cells))
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Position of the bottom right cell.
+    pub fn max(&self) -> Pos {
+        (self.rows - 1, self.cols - 1)
+    }
+
+    pub fn contains(&self, (r, c): Pos) -> bool {
+        r < self.rows && c < self.cols
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.cells[pos.0 * self.cols + pos.1])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.cells[pos.0 * self.cols + pos.1])
+        } else {
+            None
+        }
+    }
+
+    /// Access as if the grid was repeated infinitely in every direction.
+    pub fn get_wrapping(&self, (r, c): (isize, isize)) -> &T {
+        &self[self.wrap((r, c))]
+    }
+
+    pub fn get_wrapping_mut(&mut self, (r, c): (isize, isize)) -> &mut T {
+        let pos = self.wrap((r, c));
+        &mut self[pos]
+    }
+
+    fn wrap(&self, (r, c): (isize, isize)) -> Pos {
+        (
+            r.rem_euclid(self.rows as isize) as usize,
+            c.rem_euclid(self.cols as isize) as usize,
+        )
+    }
+
+    /// Moves `pos` by `(dr, dc)`, returning `None` when leaving the grid.
+    pub fn offset(&self, (r, c): Pos, (dr, dc): (isize, isize)) -> Option<Pos> {
+        let pos = (r.checked_add_signed(dr)?, c.checked_add_signed(dc)?);
+        self.contains(pos).then_some(pos)
+    }
+
+    /// Up, down, left and right neighbours inside the grid.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBOURS4
+            .into_iter()
+            .filter_map(move |d| self.offset(pos, d))
+    }
+
+    /// Neighbours inside the grid, including diagonals.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBOURS8
+            .into_iter()
+            .filter_map(move |d| self.offset(pos, d))
+    }
+
+    pub fn row(&self, r: usize) -> &[T] {
+        &self.cells[r * self.cols..(r + 1) * self.cols]
+    }
+
+    pub fn row_mut(&mut self, r: usize) -> &mut [T] {
+        &mut self.cells[r * self.cols..(r + 1) * self.cols]
+    }
+
+    pub fn column(&self, c: usize) -> impl DoubleEndedIterator<Item = &T> + '_ {
+        assert!(c < self.cols);
+        self.cells.iter().skip(c).step_by(self.cols)
+    }
+
+    pub fn row_iter(&self) -> impl DoubleEndedIterator<Item = &[T]> + '_ {
+        self.cells.chunks(self.cols.max(1))
+    }
+
+    pub fn row_iter_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut [T]> + '_ {
+        self.cells.chunks_mut(self.cols.max(1))
+    }
+
+    /// Every cell along with its position, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, v)| ((i / self.cols, i % self.cols), v))
+    }
+
+    pub fn positions<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Pos> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter_map(move |(pos, v)| (v == value).then_some(pos))
+    }
+
+    pub fn find(&self, value: &T) -> Option<Pos>
+    where
+        T: PartialEq,
+    {
+        self.positions(value).next()
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::from_vec(self.rows, self.cols, self.cells.iter().map(f).collect())
+    }
+
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.cols, self.rows, |(r, c)| (c, r))
+    }
+
+    /// Rotates a quarter turn clockwise.
+    pub fn rotate_cw(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.cols, self.rows, |(r, c)| (self.rows - 1 - c, r))
+    }
+
+    /// Rotates a quarter turn counter-clockwise.
+    pub fn rotate_ccw(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.cols, self.rows, |(r, c)| (c, self.cols - 1 - r))
+    }
+
+    // Builds a new grid where each cell is taken from `source(pos)` in this one
+    fn remap(&self, rows: usize, cols: usize, source: impl Fn(Pos) -> Pos) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..rows * cols)
+            .map(|i| self[source((i / cols, i % cols))].clone())
+            .collect();
+        Self::from_vec(rows, cols, cells)
+    }
+}
+
+impl Grid<char> {
+    /// Parses a map of characters, each of them one of `allowed`.
+    pub fn parse(input: &str, allowed: &str) -> Result<Self, ParseError> {
+        Self::parse_with(input, |cell| {
+            parse::check_chars(cell, allowed)?;
+            Ok(cell.chars().next().unwrap())
+        })
+    }
+}
+
+impl<T> std::ops::Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos:?} out of a {}x{} grid", self.rows, self.cols))
+    }
+}
+
+impl<T> std::ops::IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let (rows, cols) = (self.rows, self.cols);
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos:?} out of a {rows}x{cols} grid"))
+    }
+}
+
+impl std::fmt::Display for Grid<char> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.row_iter() {
+            writeln!(f, "{}", row.iter().collect::<String>())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        Grid::parse("abc\ndef\n", "abcdef").unwrap()
+    }
+
+    #[test]
+    fn parses_rows() {
+        let grid = grid();
+        assert_eq!((grid.rows(), grid.cols()), (2, 3));
+        assert_eq!(grid[(1, 2)], 'f');
+        assert_eq!(grid.row(1), &['d', 'e', 'f']);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(grid.to_string(), "abc\ndef\n");
+
+        let input = "#..\n..\n";
+        let err = Grid::parse(input, "#.").unwrap_err().locate(input);
+        assert_eq!(err.position(), Some((2, 1)));
+        assert_eq!(err.expected(), "a row of 3 tiles");
+
+        let input = "#..\n.x.\n";
+        let err = Grid::parse(input, "#.").unwrap_err().locate(input);
+        assert_eq!(err.position(), Some((2, 2)));
+    }
+
+    #[test]
+    fn access() {
+        let mut grid = grid();
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.get((0, 3)), None);
+        assert_eq!(grid.get_wrapping((-1, -1)), &'f');
+        assert_eq!(grid.get_wrapping((4, 7)), &'b');
+
+        *grid.get_wrapping_mut((2, 3)) = 'x';
+        assert_eq!(grid[(0, 0)], 'x');
+        assert_eq!(grid.find(&'e'), Some((1, 1)));
+        assert_eq!(grid.positions(&'z').count(), 0);
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = grid();
+        let mut n4: Vec<_> = grid.neighbours4((0, 0)).collect();
+        n4.sort();
+        assert_eq!(n4, [(0, 1), (1, 0)]);
+        assert_eq!(grid.neighbours8((0, 1)).count(), 5);
+        assert_eq!(grid.neighbours8((1, 1)).count(), 5);
+        assert_eq!(grid.offset((1, 2), (0, 1)), None);
+    }
+
+    #[test]
+    fn rotations() {
+        let grid = grid();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate_cw().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_ccw().to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+        assert_eq!(grid.transpose().transpose(), grid);
+    }
+}
@@ -0,0 +1,333 @@
+use std::ops::{Add, Range, Sub};
+
+/// Set of values stored as sorted, disjoint and non-adjacent half-open ranges.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self { ranges: vec![] }
+    }
+}
+
+impl<T: Copy + Ord> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        // Ranges overlapping or touching the new one are merged into it
+        let first = self.ranges.partition_point(|r| r.end < range.start);
+        let last = self.ranges.partition_point(|r| r.start <= range.end);
+        let merged = if first < last {
+            self.ranges[first].start.min(range.start)..self.ranges[last - 1].end.max(range.end)
+        } else {
+            range
+        };
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|r| r.start)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let idx = self.ranges.partition_point(|r| r.end <= value);
+        self.ranges.get(idx).is_some_and(|r| r.start <= value)
+    }
+
+    /// Whether every value of `range` is in the set.
+    pub fn contains_range(&self, range: &Range<T>) -> bool {
+        if range.is_empty() {
+            return true;
+        }
+        let idx = self.ranges.partition_point(|r| r.end <= range.start);
+        self.ranges
+            .get(idx)
+            .is_some_and(|r| r.start <= range.start && range.end <= r.end)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for range in &other.ranges {
+            result.insert(range.clone());
+        }
+        result
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let (mut i, mut j) = (0, 0);
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (&self.ranges[i], &other.ranges[j]);
+            let range = a.start.max(b.start)..a.end.min(b.end);
+            if !range.is_empty() {
+                ranges.push(range);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self { ranges }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let mut j = 0;
+        for range in &self.ranges {
+            let mut start = range.start;
+            while j < other.ranges.len() && other.ranges[j].end <= start {
+                j += 1;
+            }
+            // Removed ranges may extend over the next range of this set, so `j` is not advanced
+            // past the last one overlapping this range
+            let mut k = j;
+            while k < other.ranges.len() && other.ranges[k].start < range.end {
+                if other.ranges[k].start > start {
+                    ranges.push(start..other.ranges[k].start);
+                }
+                start = start.max(other.ranges[k].end);
+                k += 1;
+            }
+            if start < range.end {
+                ranges.push(start..range.end);
+            }
+        }
+        Self { ranges }
+    }
+}
+
+impl<T: Copy + Ord + Sub<Output = T> + std::iter::Sum> IntervalSet<T> {
+    /// Number of values in the set.
+    pub fn len(&self) -> T {
+        self.ranges.iter().map(|r| r.end - r.start).sum()
+    }
+}
+
+impl<T: Copy + Ord> From<Range<T>> for IntervalSet<T> {
+    fn from(range: Range<T>) -> Self {
+        std::iter::once(range).collect()
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+/// Piecewise translation: values inside a source range are offset so that the start of the range
+/// lands on its destination, values outside every range map to themselves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntervalMap<T> {
+    // Sorted by source start, never overlapping
+    pieces: Vec<(Range<T>, T)>,
+}
+
+impl<T> Default for IntervalMap<T> {
+    fn default() -> Self {
+        Self { pieces: vec![] }
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> IntervalMap<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps `source` onto the range starting at `destination`. Returns false, leaving the map
+    /// untouched, if `source` overlaps a range that was already inserted.
+    pub fn insert(&mut self, source: Range<T>, destination: T) -> bool {
+        let idx = self.pieces.partition_point(|(r, _)| r.end <= source.start);
+        if self
+            .pieces
+            .get(idx)
+            .is_some_and(|(r, _)| r.start < source.end)
+        {
+            return false;
+        }
+        if !source.is_empty() {
+            self.pieces.insert(idx, (source, destination));
+        }
+        true
+    }
+
+    pub fn get(&self, value: T) -> T {
+        let idx = self.pieces.partition_point(|(r, _)| r.end <= value);
+        match self.pieces.get(idx) {
+            Some((r, dst)) if r.start <= value => value - r.start + *dst,
+            _ => value,
+        }
+    }
+
+    /// Pieces of the translation, as source ranges and the destination of their start.
+    pub fn pieces(&self) -> &[(Range<T>, T)] {
+        &self.pieces
+    }
+
+    /// Splits `range` at the boundaries of the pieces, along with the destination of each part.
+    pub fn split(&self, range: Range<T>) -> Vec<(Range<T>, T)> {
+        let mut parts = vec![];
+        let mut start = range.start;
+        let idx = self.pieces.partition_point(|(r, _)| r.end <= start);
+        for (r, dst) in &self.pieces[idx..] {
+            if start >= range.end || r.start >= range.end {
+                break;
+            }
+            if start < r.start {
+                parts.push((start..r.start, start));
+                start = r.start;
+            }
+            let end = r.end.min(range.end);
+            parts.push((start..end, start - r.start + *dst));
+            start = end;
+        }
+        if start < range.end {
+            parts.push((start..range.end, start));
+        }
+        parts
+    }
+
+    /// Translates every value of `range`.
+    pub fn map_range(&self, range: Range<T>) -> IntervalSet<T> {
+        self.split(range)
+            .into_iter()
+            .map(|(r, dst)| dst..r.end - r.start + dst)
+            .collect()
+    }
+
+    pub fn map_set(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        set.ranges().iter().fold(IntervalSet::new(), |acc, r| {
+            acc.union(&self.map_range(r.clone()))
+        })
+    }
+
+    /// Single map translating values through this one and then through `next`.
+    pub fn then(&self, next: &Self) -> Self {
+        let mut composed = Self::new();
+        let mut add = |source: Range<T>, destination: T| {
+            if source.start != destination {
+                let inserted = composed.insert(source, destination);
+                assert!(inserted, "Composed pieces never overlap");
+            }
+        };
+
+        for (src, dst) in &self.pieces {
+            for (part, target) in next.split(*dst..src.end - src.start + *dst) {
+                add(
+                    part.start - *dst + src.start..part.end - *dst + src.start,
+                    target,
+                );
+            }
+        }
+
+        // Values left untouched by this map only go through `next`
+        let sources: IntervalSet<T> = self.pieces.iter().map(|(r, _)| r.clone()).collect();
+        for (src, dst) in &next.pieces {
+            for part in IntervalSet::from(src.clone()).difference(&sources).ranges() {
+                add(part.clone(), part.start - src.start + *dst);
+            }
+        }
+        composed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(ranges: &[Range<i64>]) -> IntervalSet<i64> {
+        ranges.iter().cloned().collect()
+    }
+
+    #[test]
+    fn normalises_ranges() {
+        let s = set(&[5..8, 0..2, 2..3, 7..10, 12..12]);
+        assert_eq!(s.ranges(), &[0..3, 5..10]);
+        assert_eq!(s.len(), 8);
+        assert_eq!(s.min(), Some(0));
+
+        assert!(s.contains(2));
+        assert!(!s.contains(3));
+        assert!(s.contains(9));
+        assert!(!s.contains(10));
+        assert!(s.contains_range(&(5..10)));
+        assert!(!s.contains_range(&(2..6)));
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = set(&[0..10, 20..30]);
+        let b = set(&[5..25, 28..40]);
+
+        assert_eq!(a.union(&b), IntervalSet::from(0..40));
+        assert_eq!(a.intersection(&b).ranges(), &[5..10, 20..25, 28..30]);
+        assert_eq!(a.difference(&b).ranges(), &[0..5, 25..28]);
+        assert_eq!(b.difference(&a).ranges(), &[10..20, 30..40]);
+        assert_eq!(a.difference(&a), IntervalSet::new());
+        assert_eq!(
+            IntervalSet::from(0..30)
+                .difference(&set(&[5..6, 7..8]))
+                .len(),
+            28
+        );
+    }
+
+    #[test]
+    fn maps_ranges() {
+        let mut map = IntervalMap::new();
+        assert!(map.insert(98..100, 50));
+        assert!(map.insert(50..98, 52));
+        assert!(!map.insert(60..61, 0));
+
+        assert_eq!(map.get(79), 81);
+        assert_eq!(map.get(99), 51);
+        assert_eq!(map.get(10), 10);
+
+        assert_eq!(map.map_range(79..93), IntervalSet::from(81..95));
+        assert_eq!(map.map_range(40..60).ranges(), &[40..50, 52..62]);
+        assert_eq!(map.map_range(97..110).ranges(), &[50..52, 99..110]);
+        assert_eq!(
+            map.map_set(&IntervalSet::from(0..200)).len(),
+            200,
+            "Translation keeps every value"
+        );
+    }
+
+    #[test]
+    fn composes_maps() {
+        let mut first = IntervalMap::new();
+        first.insert(98..100, 50);
+        first.insert(50..98, 52);
+        let mut second = IntervalMap::new();
+        second.insert(15..52, 0);
+        second.insert(52..54, 37);
+        second.insert(0..15, 39);
+
+        let composed = first.then(&second);
+        for v in 0..120 {
+            assert_eq!(composed.get(v), second.get(first.get(v)), "value {v}");
+        }
+        assert_eq!(first.then(&IntervalMap::new()), first);
+    }
+}
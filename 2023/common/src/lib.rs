@@ -0,0 +1,10 @@
+mod grid;
+mod interval;
+pub mod parse;
+pub mod search;
+mod solution;
+
+pub use grid::{Grid, Pos};
+pub use interval::{IntervalMap, IntervalSet};
+pub use parse::ParseError;
+pub use solution::{run_stdin, Answer, Part, Solution};
@@ -0,0 +1,288 @@
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Location {
+    // Address and length of the offending slice, resolved later against the whole input
+    Unresolved {
+        addr: usize,
+        len: usize,
+    },
+    Resolved {
+        line: usize,
+        column: usize,
+        len: usize,
+        text: String,
+    },
+}
+
+/// Error raised by the input parsers. It is created from a subslice of the puzzle input and
+/// resolved to a line and column with [`ParseError::locate`] once the full input is available.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    expected: String,
+    location: Location,
+    file: Option<PathBuf>,
+}
+
+impl ParseError {
+    /// `span` must be a slice of the text being parsed, pointing at the unexpected token.
+    pub fn new(span: &str, expected: impl Into<String>) -> Self {
+        Self {
+            expected: expected.into(),
+            location: Location::Unresolved {
+                addr: span.as_ptr() as usize,
+                len: span.len(),
+            },
+            file: None,
+        }
+    }
+
+    /// Points right after `span`, used when a token is missing at the end of it.
+    pub fn after(span: &str, expected: impl Into<String>) -> Self {
+        Self::new(&span[span.len()..], expected)
+    }
+
+    pub fn expected(&self) -> &str {
+        &self.expected
+    }
+
+    /// 1-based line and column of the error, if it has been located.
+    pub fn position(&self) -> Option<(usize, usize)> {
+        match &self.location {
+            Location::Resolved { line, column, .. } => Some((*line, *column)),
+            Location::Unresolved { .. } => None,
+        }
+    }
+
+    pub fn file(&self) -> Option<&Path> {
+        self.file.as_deref()
+    }
+
+    pub fn locate(mut self, input: &str) -> Self {
+        let Location::Unresolved { addr, len } = self.location else {
+            return self;
+        };
+
+        let start = input.as_ptr() as usize;
+        if addr < start || addr + len > start + input.len() {
+            return self;
+        }
+
+        let offset = addr - start;
+        let line_start = input[..offset].rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let line_end = input[offset..]
+            .find('\n')
+            .map(|i| offset + i)
+            .unwrap_or(input.len());
+
+        self.location = Location::Resolved {
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            len: input[offset..offset + len].chars().count(),
+            text: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+        };
+        self
+    }
+
+    pub fn with_file(mut self, file: impl AsRef<Path>) -> Self {
+        self.file = Some(file.as_ref().to_path_buf());
+        self
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let file = self
+            .file
+            .as_ref()
+            .map(|f| f.display().to_string())
+            .unwrap_or("<input>".to_string());
+
+        match &self.location {
+            Location::Unresolved { .. } => {
+                write!(f, "{file}: expected {}", self.expected)
+            }
+            Location::Resolved {
+                line,
+                column,
+                len,
+                text,
+            } => {
+                let width = line.to_string().len();
+                writeln!(f, "expected {}", self.expected)?;
+                writeln!(f, "{:width$}--> {file}:{line}:{column}", "")?;
+                writeln!(f, "{:width$} |", "")?;
+                writeln!(f, "{line} | {text}")?;
+                write!(
+                    f,
+                    "{:width$} | {}{}",
+                    "",
+                    " ".repeat(column - 1),
+                    "^".repeat((*len).max(1))
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+pub fn number<T: FromStr>(span: &str) -> Result<T, ParseError> {
+    span.parse().map_err(|_| ParseError::new(span, "a number"))
+}
+
+pub fn strip_prefix<'a>(s: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+    s.strip_prefix(prefix).ok_or_else(|| {
+        let len = s
+            .char_indices()
+            .nth(prefix.chars().count())
+            .map(|(i, _)| i)
+            .unwrap_or(s.len());
+        ParseError::new(&s[..len], format!("{prefix:?}"))
+    })
+}
+
+pub fn strip_suffix<'a>(s: &'a str, suffix: &str) -> Result<&'a str, ParseError> {
+    s.strip_suffix(suffix)
+        .ok_or_else(|| ParseError::after(s, format!("{suffix:?}")))
+}
+
+pub fn split_once<'a>(s: &'a str, delimiter: &str) -> Result<(&'a str, &'a str), ParseError> {
+    s.split_once(delimiter)
+        .ok_or_else(|| ParseError::after(s, format!("{delimiter:?}")))
+}
+
+/// Takes the next token from `iter`, reporting a missing token at the end of `context`.
+pub fn next<'a>(
+    iter: &mut impl Iterator<Item = &'a str>,
+    context: &'a str,
+    expected: &str,
+) -> Result<&'a str, ParseError> {
+    iter.next()
+        .ok_or_else(|| ParseError::after(context, expected))
+}
+
+/// Parses exactly `N` numbers separated by `delimiter`, ignoring surrounding whitespace.
+pub fn array<T: FromStr, const N: usize>(s: &str, delimiter: char) -> Result<[T; N], ParseError> {
+    let values = s
+        .split(delimiter)
+        .map(|v| number(v.trim()))
+        .collect::<Result<Vec<T>, _>>()?;
+    values
+        .try_into()
+        .map_err(|_| ParseError::new(s, format!("{N} numbers separated by {delimiter:?}")))
+}
+
+/// Checks that every character of `line` is one of `allowed`.
+pub fn check_chars(line: &str, allowed: &str) -> Result<(), ParseError> {
+    match line.char_indices().find(|(_, c)| !allowed.contains(*c)) {
+        Some((i, c)) => Err(ParseError::new(
+            &line[i..i + c.len_utf8()],
+            format!("one of {allowed:?}"),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Parses a rectangular map of characters, each of them one of `allowed`.
+pub fn char_grid(input: &str, allowed: &str) -> Result<Vec<Vec<char>>, ParseError> {
+    let mut width = None;
+    input
+        .lines()
+        .map(|line| {
+            check_chars(line, allowed)?;
+            let len = line.chars().count();
+            match width {
+                None => width = Some(len),
+                Some(w) if w != len => {
+                    return Err(ParseError::new(line, format!("a row of {w} tiles")));
+                }
+                _ => {}
+            }
+            Ok(line.chars().collect())
+        })
+        .collect()
+}
+
+/// Finds the single occurrence of `tile` in a map, reporting a missing tile at the end of the input.
+pub fn find_tile(input: &str, tile: char) -> Result<(usize, usize), ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .find_map(|(r, line)| line.chars().position(|c| c == tile).map(|c| (r, c)))
+        .ok_or_else(|| ParseError::after(input, format!("a {tile:?} tile")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locates_spans() {
+        let input = "Game 1: 3 blue\nGame 2: 4 grean, 1 red\n";
+        let line = input.lines().nth(1).unwrap();
+        let err = ParseError::new(&line[10..15], "a color").locate(input);
+
+        assert_eq!(err.position(), Some((2, 11)));
+        assert_eq!(
+            err.with_file("day2.txt").to_string(),
+            "expected a color\n \
+             --> day2.txt:2:11\n  \
+             |\n\
+             2 | Game 2: 4 grean, 1 red\n  \
+             |           ^^^^^"
+        );
+    }
+
+    #[test]
+    fn missing_tokens_point_past_the_end() {
+        let input = "seeds: 1 2\n\nseed-to-soil map:\n50 98\n";
+        let line = input.lines().nth(3).unwrap();
+        let mut iter = line.split_whitespace();
+        let _ = next(&mut iter, line, "destination").unwrap();
+        let _ = next(&mut iter, line, "source").unwrap();
+        let err = next(&mut iter, line, "length").unwrap_err().locate(input);
+
+        assert_eq!(err.position(), Some((4, 6)));
+        assert_eq!(err.expected(), "length");
+    }
+
+    #[test]
+    fn foreign_spans_stay_unresolved() {
+        let owned = String::from("12x");
+        let err = number::<usize>(&owned).unwrap_err().locate("12x");
+        assert_eq!(err.position(), None);
+    }
+
+    #[test]
+    fn helpers() {
+        assert_eq!(number::<usize>("42"), Ok(42));
+        assert_eq!(strip_prefix("Game 1", "Game "), Ok("1"));
+        assert_eq!(split_once("a -> b", " -> "), Ok(("a", "b")));
+        assert!(check_chars("#..#", ".#").is_ok());
+        assert_eq!(array::<usize, 3>("1, 2,3", ','), Ok([1, 2, 3]));
+        assert!(array::<usize, 3>("1,2", ',').is_err());
+
+        let input = "#..x";
+        let err = check_chars(input, ".#").unwrap_err().locate(input);
+        assert_eq!(err.position(), Some((1, 4)));
+
+        let err = strip_prefix(input, "Time:").unwrap_err().locate(input);
+        assert_eq!(err.position(), Some((1, 1)));
+    }
+
+    #[test]
+    fn grids() {
+        let input = "#.S\n...\n";
+        assert_eq!(char_grid(input, "#.S").unwrap().len(), 2);
+        assert_eq!(find_tile(input, 'S'), Ok((0, 2)));
+
+        let input = "#..\n..\n";
+        let err = char_grid(input, "#.").unwrap_err().locate(input);
+        assert_eq!(err.position(), Some((2, 1)));
+        assert_eq!(err.expected(), "a row of 3 tiles");
+    }
+}
@@ -0,0 +1,125 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+
+/// Cheapest route found by [`dijkstra`] or [`astar`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<S> {
+    pub cost: usize,
+    /// Every state visited, from the start to the goal, both included.
+    pub states: Vec<S>,
+}
+
+/// Finds the cheapest path from `start` to any state satisfying `goal`. `successors` yields the
+/// states reachable from a given one along with the cost of the move.
+pub fn dijkstra<S, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    astar(start, successors, goal, |_| 0)
+}
+
+/// Same as [`dijkstra`], guided by `heuristic`. The heuristic must never overestimate the
+/// remaining cost to the goal, otherwise the returned path might not be the cheapest.
+pub fn astar<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut goal: impl FnMut(&S) -> bool,
+    mut heuristic: impl FnMut(&S) -> usize,
+) -> Option<Path<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    // States are numbered in discovery order so the bookkeeping below only stores indices
+    let mut states = vec![start.clone()];
+    let mut index = HashMap::from([(start.clone(), 0)]);
+    let mut best = vec![0];
+    let mut parent = vec![None];
+
+    let mut queue = BinaryHeap::new();
+    queue.push(Reverse((heuristic(&start), 0, 0)));
+
+    while let Some(Reverse((_, cost, idx))) = queue.pop() {
+        if cost > best[idx] {
+            // Stale entry, a cheaper way to this state was found after pushing it
+            continue;
+        }
+
+        if goal(&states[idx]) {
+            let mut path = vec![];
+            let mut cur = Some(idx);
+            while let Some(i) = cur {
+                path.push(states[i].clone());
+                cur = parent[i];
+            }
+            path.reverse();
+            return Some(Path { cost, states: path });
+        }
+
+        for (next, step) in successors(&states[idx]) {
+            let next_cost = cost + step;
+            let next_idx = match index.get(&next) {
+                Some(&i) if best[i] <= next_cost => continue,
+                Some(&i) => i,
+                None => {
+                    states.push(next.clone());
+                    best.push(usize::MAX);
+                    parent.push(None);
+                    index.insert(next, states.len() - 1);
+                    states.len() - 1
+                }
+            };
+
+            best[next_idx] = next_cost;
+            parent[next_idx] = Some(idx);
+            let estimate = next_cost + heuristic(&states[next_idx]);
+            queue.push(Reverse((estimate, next_cost, next_idx)));
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Grid, Pos};
+
+    fn successors(grid: &Grid<char>, pos: &Pos) -> Vec<(Pos, usize)> {
+        grid.neighbours4(*pos)
+            .filter(|n| grid[*n] != '#')
+            .map(|n| (n, if grid[n] == '~' { 5 } else { 1 }))
+            .collect()
+    }
+
+    #[test]
+    fn cheapest_path() {
+        let grid = Grid::parse("..~.\n....\n", ".~").unwrap();
+        let end = (0, 3);
+
+        let path = dijkstra((0, 0), |p| successors(&grid, p), |p| *p == end).unwrap();
+        assert_eq!(path.cost, 5);
+        assert_eq!(path.states.first(), Some(&(0, 0)));
+        assert_eq!(path.states.last(), Some(&end));
+        assert_eq!(path.states.len(), 6);
+
+        let manhattan = |p: &Pos| p.0.abs_diff(end.0) + p.1.abs_diff(end.1);
+        let guided = astar((0, 0), |p| successors(&grid, p), |p| *p == end, manhattan);
+        assert_eq!(guided.map(|p| p.cost), Some(5));
+    }
+
+    #[test]
+    fn unreachable_goal() {
+        let grid = Grid::parse(".#.\n##.\n", ".#").unwrap();
+        assert_eq!(
+            dijkstra((0, 0), |p| successors(&grid, p), |p| *p == (1, 2)),
+            None
+        );
+    }
+}
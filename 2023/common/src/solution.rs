@@ -0,0 +1,107 @@
+use std::io::Read;
+
+use crate::ParseError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl std::str::FromStr for Part {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            p => anyhow::bail!("Unknown part {p:?}, expected 1 or 2"),
+        }
+    }
+}
+
+impl std::fmt::Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Unsigned(u64),
+    Signed(i64),
+    Text(String),
+}
+
+impl std::fmt::Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Unsigned(v) => write!(f, "{v}"),
+            Answer::Signed(v) => write!(f, "{v}"),
+            Answer::Text(v) => write!(f, "{v}"),
+        }
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::Unsigned(value as u64)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(value: u64) -> Self {
+        Answer::Unsigned(value)
+    }
+}
+
+impl From<isize> for Answer {
+    fn from(value: isize) -> Self {
+        Answer::Signed(value as i64)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Self {
+        Answer::Signed(value)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+/// A single day of the puzzle. The input is parsed once and shared by both parts.
+pub trait Solution {
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    fn part1(input: &Self::Input) -> Answer;
+
+    fn part2(input: &Self::Input) -> Answer;
+
+    fn solve(input: &str, part: Part) -> anyhow::Result<Answer> {
+        let input = Self::parse(input).map_err(|e| e.locate(input))?;
+        Ok(match part {
+            Part::One => Self::part1(&input),
+            Part::Two => Self::part2(&input),
+        })
+    }
+}
+
+/// Entry point shared by the per-day binaries: reads the puzzle from stdin and prints both parts.
+pub fn run_stdin<S: Solution>() -> anyhow::Result<()> {
+    let mut text = String::new();
+    std::io::stdin().lock().read_to_string(&mut text)?;
+
+    let input = S::parse(&text).map_err(|e| e.locate(&text))?;
+    println!("part 1: {}", S::part1(&input));
+    println!("part 2: {}", S::part2(&input));
+    Ok(())
+}
@@ -0,0 +1,158 @@
+use common::{Answer, ParseError, Solution};
+
+fn check_seq<T: std::iter::Iterator<Item = char> + Clone>(iter: &T, chars: &str) -> bool {
+    let new_iter = (*iter).clone();
+    new_iter.take(chars.len()).eq(chars.chars())
+}
+
+fn find_first(line: &str) -> usize {
+    let mut iter = line.chars();
+    loop {
+        match iter.next().unwrap() {
+            c if c.is_numeric() => {
+                return (c as u8 - b'0') as usize;
+            }
+            'o' if check_seq(&iter, "ne") => {
+                return 1;
+            }
+            't' if check_seq(&iter, "wo") => {
+                return 2;
+            }
+            't' if check_seq(&iter, "hree") => {
+                return 3;
+            }
+            'f' if check_seq(&iter, "our") => {
+                return 4;
+            }
+            'f' if check_seq(&iter, "ive") => {
+                return 5;
+            }
+            's' if check_seq(&iter, "ix") => {
+                return 6;
+            }
+            's' if check_seq(&iter, "even") => {
+                return 7;
+            }
+            'e' if check_seq(&iter, "ight") => {
+                return 8;
+            }
+            'n' if check_seq(&iter, "ine") => {
+                return 9;
+            }
+            _ => {}
+        }
+    }
+}
+
+fn find_last(line: &str) -> usize {
+    let mut iter = line.chars().rev();
+    loop {
+        match iter.next().unwrap() {
+            c if c.is_numeric() => {
+                return (c as u8 - b'0') as usize;
+            }
+            'e' if check_seq(&iter, "no") => {
+                return 1;
+            }
+            'o' if check_seq(&iter, "wt") => {
+                return 2;
+            }
+            'e' if check_seq(&iter, "erht") => {
+                return 3;
+            }
+            'r' if check_seq(&iter, "uof") => {
+                return 4;
+            }
+            'e' if check_seq(&iter, "vif") => {
+                return 5;
+            }
+            'x' if check_seq(&iter, "is") => {
+                return 6;
+            }
+            'n' if check_seq(&iter, "eves") => {
+                return 7;
+            }
+            't' if check_seq(&iter, "hgie") => {
+                return 8;
+            }
+            'e' if check_seq(&iter, "nin") => {
+                return 9;
+            }
+            _ => {}
+        }
+    }
+}
+
+fn digits_only(line: &str) -> usize {
+    let mut digits = line.chars().filter_map(|c| c.to_digit(10));
+    let first = digits.next().unwrap_or(0) as usize;
+    let last = digits.next_back().map(|d| d as usize).unwrap_or(first);
+    first * 10 + last
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        const DIGITS: [&str; 9] = [
+            "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+        ];
+
+        input
+            .lines()
+            .map(|l| {
+                if l.chars().any(|c| c.is_ascii_digit()) || DIGITS.iter().any(|d| l.contains(d)) {
+                    Ok(l.to_owned())
+                } else {
+                    Err(ParseError::new(l, "a digit or a spelled out digit"))
+                }
+            })
+            .collect()
+    }
+
+    fn part1(lines: &Self::Input) -> Answer {
+        lines.iter().map(|l| digits_only(l)).sum::<usize>().into()
+    }
+
+    fn part2(lines: &Self::Input) -> Answer {
+        let mut acc = 0;
+        for line in lines.iter() {
+            let first = find_first(line);
+            let last = find_last(line);
+            let num = first * 10 + last;
+
+            acc += num;
+        }
+        acc.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet
+";
+
+    const WORDS: &str = "two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen
+";
+
+    #[test]
+    fn solves_example() {
+        let example = Day1::parse(EXAMPLE).unwrap();
+        assert_eq!(Day1::part1(&example), Answer::from(142_usize));
+        let words = Day1::parse(WORDS).unwrap();
+        assert_eq!(Day1::part2(&words), Answer::from(281_usize));
+    }
+}
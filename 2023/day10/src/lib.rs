@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+
+use common::parse;
+use common::{Answer, Grid, ParseError, Pos, Solution};
+
+const UP: (isize, isize) = (-1, 0);
+const DOWN: (isize, isize) = (1, 0);
+const LEFT: (isize, isize) = (0, -1);
+const RIGHT: (isize, isize) = (0, 1);
+
+#[derive(Debug)]
+pub struct Map(Grid<char>);
+
+struct Contour(HashMap<Pos, char>);
+
+impl Map {
+    fn at(&self, c: Pos) -> char {
+        self.0[c]
+    }
+
+    fn step(&self, c: Pos, dir: (isize, isize)) -> Option<Pos> {
+        self.0.offset(c, dir)
+    }
+
+    fn find_start(&self) -> Pos {
+        self.0.find(&'S').unwrap()
+    }
+
+    fn first_step(&self, start: Pos) -> (Pos, char) {
+        let connects = |dir, pipes: &str| {
+            self.step(start, dir)
+                .is_some_and(|c| pipes.contains(self.at(c)))
+        };
+        let up = connects(UP, "|F7");
+        let down = connects(DOWN, "|LJ");
+        let left = connects(LEFT, "-LF");
+        let right = connects(RIGHT, "-7J");
+
+        let (dir, subs) = match (up, down, left, right) {
+            (true, true, false, false) => (UP, '|'),
+            (true, false, false, true) => (UP, 'L'),
+            (true, false, true, false) => (UP, 'J'),
+            (false, true, true, false) => (LEFT, '7'),
+            (false, true, false, true) => (RIGHT, 'F'),
+            (false, false, true, true) => (LEFT, '-'),
+            c => {
+                panic!("Invalid surroundings {c:?}");
+            }
+        };
+        (self.step(start, dir).unwrap(), subs)
+    }
+
+    fn next(&self, c: Pos) -> (Pos, Pos) {
+        let (a, b) = match self.at(c) {
+            '-' => (LEFT, RIGHT),
+            '|' => (UP, DOWN),
+            'F' => (RIGHT, DOWN),
+            'L' => (RIGHT, UP),
+            '7' => (LEFT, DOWN),
+            'J' => (LEFT, UP),
+            _ => unimplemented!(),
+        };
+        (self.step(c, a).unwrap(), self.step(c, b).unwrap())
+    }
+}
+
+fn build_contour(map: &Map) -> Contour {
+    let mut contour = HashMap::new();
+    let s = map.find_start();
+
+    let mut steps = 1;
+    let mut prev = s;
+
+    // Derive first step
+    let (mut position, subs) = map.first_step(s);
+    contour.insert(position, map.at(position));
+    contour.insert(s, subs);
+
+    while position != s || steps == 0 {
+        let next = match map.next(position) {
+            (a, b) if a == prev => b,
+            (a, b) if b == prev => a,
+            _ => unimplemented!(),
+        };
+
+        if map.at(next) != 'S' {
+            contour.insert(next, map.at(next));
+        }
+
+        prev = position;
+        position = next;
+
+        steps += 1;
+    }
+
+    Contour(contour)
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Map;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let grid = Grid::parse(input, "|-LJ7F.S")?;
+        parse::find_tile(input, 'S')?;
+        Ok(Map(grid))
+    }
+
+    fn part1(map: &Self::Input) -> Answer {
+        let contour = build_contour(map);
+        (contour.0.len() / 2).into()
+    }
+
+    fn part2(map: &Self::Input) -> Answer {
+        let contour = build_contour(map);
+
+        // |iL---JiiiL--7oo
+        // count inner tiles
+        let mut count = 0usize;
+        for row in 0..map.0.rows() {
+            let mut inner = false;
+            let mut last_bound = None;
+            for col in 0..map.0.cols() {
+                let c = contour.0.get(&(row, col)).unwrap_or(&'.');
+                match c {
+                    '-' => {
+                        // No changes
+                    }
+                    '|' => {
+                        inner = !inner;
+                        last_bound = None;
+                    }
+                    'F' | 'L' => {
+                        last_bound = Some(c);
+                    }
+                    '7' | 'J' => {
+                        match (last_bound.unwrap(), c) {
+                            ('F', 'J') | ('L', '7') => {
+                                inner = !inner;
+                            }
+                            ('F', '7') | ('L', 'J') => {}
+                            c => {
+                                panic!("What?: {c:?}");
+                            }
+                        };
+                        last_bound = None;
+                    }
+                    '.' => {
+                        if inner {
+                            count += 1;
+                        }
+                    }
+                    _ => unimplemented!(),
+                }
+            }
+        }
+        count.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "..F7.
+.FJ|.
+SJ.L7
+|F--J
+LJ...
+";
+
+    const ENCLOSED: &str = "FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L
+";
+
+    #[test]
+    fn solves_example() {
+        let example = Day10::parse(EXAMPLE).unwrap();
+        assert_eq!(Day10::part1(&example), Answer::from(8_usize));
+        let enclosed = Day10::parse(ENCLOSED).unwrap();
+        assert_eq!(Day10::part2(&enclosed), Answer::from(10_usize));
+    }
+}
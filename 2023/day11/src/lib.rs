@@ -0,0 +1,104 @@
+use common::{Answer, Grid, ParseError, Pos, Solution};
+
+pub struct Map(Grid<char>);
+
+impl std::fmt::Display for Map {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+fn collect_empty_rows(v: &Map) -> Vec<usize> {
+    (0..v.0.rows())
+        .filter(|r| v.0.row(*r).iter().all(|c| *c == '.'))
+        .collect()
+}
+
+fn collect_empty_cols(v: &Map) -> Vec<usize> {
+    (0..v.0.cols())
+        .filter(|c| v.0.column(*c).all(|c| *c == '.'))
+        .collect()
+}
+
+fn collect_stars(v: &Map) -> Vec<Pos> {
+    v.0.positions(&'#').collect()
+}
+
+fn distance(
+    a: Pos,
+    b: Pos,
+    empty_rows: &[usize],
+    empty_cols: &[usize],
+    expansion_factor: usize,
+) -> usize {
+    let (first_row, last_row) = if a.0 >= b.0 { (b.0, a.0) } else { (a.0, b.0) };
+    let (first_col, last_col) = if a.1 >= b.1 { (b.1, a.1) } else { (a.1, b.1) };
+
+    let num_empty_rows = empty_rows
+        .iter()
+        .filter(|r_idx| (first_row < **r_idx) && (last_row > **r_idx))
+        .count();
+    let num_empty_cols = empty_cols
+        .iter()
+        .filter(|c_idx| (first_col < **c_idx) && (last_col > **c_idx))
+        .count();
+    let col_dist = last_col - first_col;
+    let row_dist = last_row - first_row;
+    col_dist + row_dist + (num_empty_cols + num_empty_rows) * (expansion_factor - 1)
+}
+
+fn total_distance(map: &Map, expansion_factor: usize) -> usize {
+    let star_coords = collect_stars(map);
+    let empty_rows = collect_empty_rows(map);
+    let empty_cols = collect_empty_cols(map);
+
+    let mut total = 0;
+    for (idx, first) in star_coords.iter().enumerate() {
+        for second in star_coords.iter().skip(idx + 1) {
+            total += distance(*first, *second, &empty_rows, &empty_cols, expansion_factor);
+        }
+    }
+    total
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Map;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(Map(Grid::parse(input, ".#")?))
+    }
+
+    fn part1(map: &Self::Input) -> Answer {
+        total_distance(map, 2).into()
+    }
+
+    fn part2(map: &Self::Input) -> Answer {
+        total_distance(map, 1_000_000).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....
+";
+
+    #[test]
+    fn solves_example() {
+        let input = Day11::parse(EXAMPLE).unwrap();
+        assert_eq!(Day11::part1(&input), Answer::from(374_usize));
+        assert_eq!(Day11::part2(&input), Answer::from(82000210_usize));
+    }
+}
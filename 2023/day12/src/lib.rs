@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+
+use common::parse;
+use common::{Answer, ParseError, Solution};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+enum State {
+    NoGroup,
+    Group,
+}
+
+#[derive(Hash, Eq, PartialEq)]
+struct Key {
+    state: State,
+    damaged: Vec<usize>,
+    s: String,
+}
+
+fn count_arrangements(
+    s: &str,
+    state: State,
+    damaged: &mut [usize],
+    cache: &mut HashMap<Key, usize>,
+) -> usize {
+    if (damaged.len() == 1 && damaged[0] == 0) || damaged.is_empty() {
+        if s.chars().any(|c| c == '#') {
+            // no failures left, but there are failures in the remainder of the str
+            return 0;
+        } else {
+            return 1;
+        }
+    }
+
+    let k = Key {
+        s: s.to_owned(),
+        state,
+        damaged: damaged.to_owned(),
+    };
+
+    if let Some(r) = cache.get(&k) {
+        return *r;
+    }
+
+    let solution = match (state, s.split_at(1)) {
+        (_, ("?", _)) => {
+            let with_fail: String = s
+                .chars()
+                .enumerate()
+                .map(|(i, c)| if i == 0 { '#' } else { c })
+                .collect();
+            let without_fail: String = s
+                .chars()
+                .enumerate()
+                .map(|(i, c)| if i == 0 { '.' } else { c })
+                .collect();
+            let fail = count_arrangements(&with_fail, state, damaged, cache);
+            let no_fail = count_arrangements(&without_fail, state, damaged, cache);
+            fail + no_fail
+        }
+        (_, ("#", "")) => {
+            if damaged.len() == 1 && damaged[0] == 1 {
+                1
+            } else {
+                0
+            }
+        }
+        (State::Group, (".", "")) => {
+            if damaged.len() == 1 && damaged[0] == 0 {
+                1
+            } else {
+                0
+            }
+        }
+        (State::NoGroup, (".", "")) => {
+            if damaged.is_empty() {
+                1
+            } else {
+                0
+            }
+        }
+        (_, ("#", rest)) => {
+            if damaged.is_empty() || damaged[0] == 0 {
+                // Not possible
+                0
+            } else {
+                damaged[0] -= 1;
+                let res = count_arrangements(rest, State::Group, damaged, cache);
+                damaged[0] += 1;
+                res
+            }
+        }
+        (State::NoGroup, (".", rest)) => count_arrangements(rest, State::NoGroup, damaged, cache),
+        (State::Group, (".", rest)) => {
+            assert!(!damaged.is_empty());
+            if damaged[0] != 0 {
+                // Not possible, failure
+                0
+            } else {
+                count_arrangements(rest, State::NoGroup, &mut damaged[1..], cache)
+            }
+        }
+        p => {
+            panic!("Unexpected pattern {p:?}");
+        }
+    };
+    cache.insert(k, solution);
+    solution
+}
+
+pub struct Record {
+    pattern: String,
+    damaged: Vec<usize>,
+}
+
+fn unfold(record: &Record) -> Record {
+    let pattern = [record.pattern.as_str(); 5].join("?");
+    let damaged = record.damaged.repeat(5);
+    Record { pattern, damaged }
+}
+
+fn count_record(record: &Record) -> usize {
+    let mut cache = HashMap::new();
+    let mut damaged = record.damaged.clone();
+    let arr = count_arrangements(&record.pattern, State::NoGroup, &mut damaged, &mut cache);
+    assert_eq!(record.damaged, damaged);
+    arr
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Vec<Record>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input
+            .lines()
+            .map(|line| {
+                let (pattern, damaged) = parse::split_once(line, " ")?;
+                parse::check_chars(pattern, ".#?")?;
+                let damaged = damaged
+                    .split(',')
+                    .map(parse::number)
+                    .collect::<Result<Vec<usize>, _>>()?;
+                Ok(Record {
+                    pattern: pattern.to_string(),
+                    damaged,
+                })
+            })
+            .collect()
+    }
+
+    fn part1(records: &Self::Input) -> Answer {
+        records.iter().map(count_record).sum::<usize>().into()
+    }
+
+    fn part2(records: &Self::Input) -> Answer {
+        records
+            .iter()
+            .map(|r| count_record(&unfold(r)))
+            .sum::<usize>()
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "???.### 1,1,3
+.??..??...?##. 1,1,3
+?#?#?#?#?#?#?#? 1,3,1,6
+????.#...#... 4,1,1
+????.######..#####. 1,6,5
+?###???????? 3,2,1
+";
+
+    #[test]
+    fn solves_example() {
+        let input = Day12::parse(EXAMPLE).unwrap();
+        assert_eq!(Day12::part1(&input), Answer::from(21_usize));
+        assert_eq!(Day12::part2(&input), Answer::from(525152_usize));
+    }
+}
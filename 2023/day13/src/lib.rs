@@ -0,0 +1,172 @@
+use common::parse;
+use common::{Answer, ParseError, Solution};
+
+type Pattern = Vec<Vec<char>>;
+
+fn parse_patterns(lines: &[&str]) -> Result<Vec<Pattern>, ParseError> {
+    let mut patterns = vec![];
+    let mut current_pattern = vec![];
+    for line in lines {
+        if line.trim() == "" {
+            patterns.push(current_pattern);
+            current_pattern = vec![];
+            continue;
+        }
+
+        parse::check_chars(line, ".#")?;
+        current_pattern.push(line.chars().collect());
+    }
+
+    if !current_pattern.is_empty() {
+        patterns.push(current_pattern)
+    }
+
+    Ok(patterns)
+}
+
+fn print_matrix<T, U, V>(matrix: V)
+where
+    V: AsRef<[U]>,
+    U: AsRef<[T]>,
+    T: std::fmt::Display,
+{
+    println!("[");
+    for line in matrix.as_ref().iter() {
+        print!("\t");
+        for c in line.as_ref().iter() {
+            print!("{c}, ");
+        }
+        println!()
+    }
+    println!("]");
+}
+
+pub fn print_patterns<U, T, V, Y>(patterns: Y)
+where
+    Y: AsRef<[V]>,
+    V: AsRef<[U]>,
+    U: AsRef<[T]>,
+    T: std::fmt::Display,
+{
+    for (i, pattern) in patterns.as_ref().iter().enumerate() {
+        println!("Pattern {i}");
+        print_matrix(pattern);
+    }
+}
+
+fn check_diffs(l: &[char], r: &[char]) -> usize {
+    assert_eq!(l.len(), r.len());
+    l.iter()
+        .zip(r.iter())
+        .fold(0, |acc, (l, r)| if l == r { acc } else { acc + 1 })
+}
+
+fn find_horizontal_reflection(pattern: &Pattern, smudges: usize) -> Option<usize> {
+    for pivot in 0..pattern.len() - 1 {
+        let mut i = pivot;
+        let mut j = pivot + 1;
+        let mut diffs = 0;
+
+        // We consider the pivot and walk i and j outwards until they don't match or
+        // one of the indexes is already going to fall out of the range
+
+        loop {
+            diffs += check_diffs(&pattern[i], &pattern[j]);
+            if diffs > smudges {
+                break;
+            }
+
+            if (i == 0) || (j == pattern.len() - 1) {
+                if diffs == smudges {
+                    return Some(pivot);
+                } else {
+                    break;
+                }
+            }
+            i -= 1;
+            j += 1;
+        }
+    }
+    None
+}
+
+fn transpose(pattern: &Pattern) -> Pattern {
+    let rows = pattern.len();
+    let cols = pattern[0].len();
+    let mut res = vec![vec![' '; rows]; cols];
+
+    for (i, row) in pattern.iter().enumerate() {
+        for (j, elem) in row.iter().enumerate() {
+            res[j][i] = *elem;
+        }
+    }
+
+    res
+}
+
+fn find_vertical_reflection(pattern: &Pattern, smudges: usize) -> Option<usize> {
+    let pattern = transpose(pattern);
+    find_horizontal_reflection(&pattern, smudges)
+}
+
+fn summarize(patterns: &[Pattern], smudges: usize) -> usize {
+    let mut count = 0;
+    for pattern in patterns {
+        if let Some(pivot) = find_vertical_reflection(pattern, smudges) {
+            count += pivot + 1;
+        }
+
+        if let Some(pivot) = find_horizontal_reflection(pattern, smudges) {
+            count += 100 * (pivot + 1);
+        }
+    }
+    count
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Vec<Pattern>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let lines: Vec<_> = input.lines().collect();
+        parse_patterns(&lines)
+    }
+
+    fn part1(patterns: &Self::Input) -> Answer {
+        summarize(patterns, 0).into()
+    }
+
+    fn part2(patterns: &Self::Input) -> Answer {
+        summarize(patterns, 1).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "#.##..##.
+..#.##.#.
+##......#
+##......#
+..#.##.#.
+..##..##.
+#.#.##.#.
+
+#...##..#
+#....#..#
+..##..###
+#####.##.
+#####.##.
+..##..###
+#....#..#
+";
+
+    #[test]
+    fn solves_example() {
+        let input = Day13::parse(EXAMPLE).unwrap();
+        assert_eq!(Day13::part1(&input), Answer::from(405_usize));
+        assert_eq!(Day13::part2(&input), Answer::from(400_usize));
+    }
+}
@@ -0,0 +1,155 @@
+use common::{Answer, Grid, ParseError, Solution};
+
+#[derive(Copy, Clone, Debug)]
+enum Dir {
+    North,
+    South,
+    West,
+    East,
+}
+
+fn tilt(map: &Grid<char>, dir: Dir) -> Grid<char> {
+    let tilt_rows = |map: &Grid<char>, reverse: bool| {
+        let mut new = map.clone();
+
+        for row in new.row_iter_mut() {
+            let mut num_balls = 0;
+            let copied_row = row.to_vec();
+            let iter = if reverse {
+                Box::new(copied_row.iter().enumerate().rev())
+                    as Box<dyn Iterator<Item = (usize, &char)>>
+            } else {
+                Box::new(copied_row.iter().enumerate()) as Box<dyn Iterator<Item = (usize, &char)>>
+            };
+            for (idx, c) in iter {
+                match c {
+                    'O' => {
+                        num_balls += 1;
+                        row[idx] = '.';
+                    }
+                    '#' => {
+                        row[idx] = '#';
+                        for i in 0..num_balls {
+                            let ball_idx = if reverse { idx + 1 + i } else { idx - 1 - i };
+                            row[ball_idx] = 'O';
+                        }
+                        num_balls = 0;
+                    }
+                    '.' => {}
+                    c => panic!("Unexpected character {c}"),
+                };
+            }
+
+            for i in 0..num_balls {
+                let ball_idx = if reverse { i } else { row.len() - 1 - i };
+                row[ball_idx] = 'O';
+            }
+        }
+
+        new
+    };
+
+    let (t, r) = match dir {
+        Dir::North => (true, true),
+        Dir::South => (true, false),
+        Dir::West => (false, true),
+        Dir::East => (false, false),
+    };
+
+    // Columns are tilted as the rows of the transposed map
+    if t {
+        tilt_rows(&map.transpose(), r).transpose()
+    } else {
+        tilt_rows(map, r)
+    }
+}
+
+enum CycleResult {
+    Unique(Grid<char>),
+    Cycle(usize),
+}
+
+fn cycle_until_stable(mut map: Grid<char>, steps: usize) -> Grid<char> {
+    let run = |map: &Grid<char>, memory: &mut Vec<Grid<char>>| {
+        let mut new = tilt(map, Dir::North);
+        new = tilt(&new, Dir::West);
+        new = tilt(&new, Dir::South);
+        new = tilt(&new, Dir::East);
+        if let Some((i, _)) = memory.iter().enumerate().find(|(_, m)| **m == new) {
+            return CycleResult::Cycle(i);
+        }
+
+        memory.push(new.clone());
+        CycleResult::Unique(new)
+    };
+
+    let mut memory: Vec<Grid<char>> = vec![];
+
+    let mut i = 0;
+    let (start, cycle) = loop {
+        map = match run(&map, &mut memory) {
+            CycleResult::Unique(s) => s,
+            CycleResult::Cycle(old_idx) => {
+                break (old_idx, i - old_idx);
+            }
+        };
+        i += 1;
+    };
+
+    let remaining = steps - i - 1;
+    let idx = remaining % cycle;
+    memory[start + idx].clone()
+}
+
+fn weight(map: &Grid<char>) -> usize {
+    let mut value = 0;
+    let len = map.rows();
+    for (i, row) in map.row_iter().enumerate() {
+        let count = row.iter().filter(|c| **c == 'O').count();
+        value += (len - i) * count;
+    }
+    value
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Grid<char>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Grid::parse(input, "O.#")
+    }
+
+    fn part1(map: &Self::Input) -> Answer {
+        weight(&tilt(map, Dir::North)).into()
+    }
+
+    fn part2(map: &Self::Input) -> Answer {
+        let map = cycle_until_stable(map.clone(), 1_000_000_000);
+        weight(&map).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#....
+";
+
+    #[test]
+    fn solves_example() {
+        let input = Day14::parse(EXAMPLE).unwrap();
+        assert_eq!(Day14::part1(&input), Answer::from(136_usize));
+        assert_eq!(Day14::part2(&input), Answer::from(64_usize));
+    }
+}
@@ -0,0 +1,106 @@
+use common::parse;
+use common::{Answer, ParseError, Solution};
+
+type Label = String;
+type FocalLength = usize;
+type Box = Vec<(Label, FocalLength)>;
+
+struct Boxes(Vec<Box>);
+
+impl Boxes {
+    fn new() -> Self {
+        Self(vec![vec![]; 256])
+    }
+
+    fn get_mut(&mut self, idx: usize) -> &mut Box {
+        &mut self.0[idx]
+    }
+}
+
+fn hash(step: &str) -> usize {
+    step.bytes()
+        .fold(0usize, |acc, value| ((acc + value as usize) * 17) % 256)
+}
+
+fn handle_instruction(inst: &str, boxes: &mut Boxes) {
+    if inst.contains('-') {
+        let label = inst.split('-').next().unwrap();
+        let box_idx = hash(label);
+        let list = boxes.get_mut(box_idx);
+
+        if let Some(i) = list
+            .iter()
+            .enumerate()
+            .find_map(|(i, (l, _fl))| if l == label { Some(i) } else { None })
+        {
+            list.remove(i);
+        }
+    } else {
+        let label = inst.split('=').next().unwrap();
+        let new_fl = inst.split('=').nth(1).unwrap().parse().unwrap();
+        let box_idx = hash(label);
+        let list = boxes.get_mut(box_idx);
+
+        if let Some((_l, fl)) = list.iter_mut().find(|(l, _fl)| l == label) {
+            *fl = new_fl;
+        } else {
+            list.push((label.to_string(), new_fl));
+        }
+    }
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input
+            .trim()
+            .split(',')
+            .map(|step| {
+                if let Some((label, focal_length)) = step.split_once('=') {
+                    parse::check_chars(label, "abcdefghijklmnopqrstuvwxyz")?;
+                    parse::number::<FocalLength>(focal_length)?;
+                } else {
+                    let label = parse::strip_suffix(step, "-")?;
+                    parse::check_chars(label, "abcdefghijklmnopqrstuvwxyz")?;
+                }
+                Ok(step.to_string())
+            })
+            .collect()
+    }
+
+    fn part1(steps: &Self::Input) -> Answer {
+        steps.iter().map(|s| hash(s)).sum::<usize>().into()
+    }
+
+    fn part2(steps: &Self::Input) -> Answer {
+        let boxes = steps.iter().fold(Boxes::new(), |mut boxes, inst| {
+            handle_instruction(inst, &mut boxes);
+            boxes
+        });
+
+        let count = boxes.0.iter().enumerate().fold(0, |acc, (box_idx, b)| {
+            b.iter().enumerate().fold(acc, |acc, (slot_idx, (_l, fl))| {
+                acc + (box_idx + 1) * (slot_idx + 1) * fl
+            })
+        });
+        count.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7
+";
+
+    #[test]
+    fn solves_example() {
+        let input = Day15::parse(EXAMPLE).unwrap();
+        assert_eq!(Day15::part1(&input), Answer::from(1320_usize));
+        assert_eq!(Day15::part2(&input), Answer::from(145_usize));
+    }
+}
@@ -0,0 +1,161 @@
+use common::{Answer, Grid, ParseError, Solution};
+
+#[derive(Debug)]
+pub struct Map(Grid<char>);
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Energy {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+#[derive(Debug)]
+struct EnergyMap(Grid<Vec<Energy>>);
+
+impl EnergyMap {
+    fn new(rows: usize, cols: usize) -> Self {
+        Self(Grid::new(rows, cols, vec![]))
+    }
+
+    fn insert(&mut self, (row, col, dir): (usize, usize, Energy)) -> bool {
+        let cell = &mut self.0[(row, col)];
+        if cell.contains(&dir) {
+            // Already taken, no need to revisit
+            return true;
+        }
+        cell.push(dir);
+        false
+    }
+
+    fn energized(&self) -> usize {
+        self.0.iter().filter(|(_, v)| !v.is_empty()).count()
+    }
+}
+
+fn energy_to_incr(dir: Energy) -> (isize, isize) {
+    match dir {
+        Energy::Up => (-1, 0),
+        Energy::Down => (1, 0),
+        Energy::Left => (0, -1),
+        Energy::Right => (0, 1),
+    }
+}
+
+fn next_energy_options(pipe: char, energy: Energy) -> Vec<Energy> {
+    match pipe {
+        '.' => vec![energy],
+        '/' => vec![match energy {
+            Energy::Up => Energy::Right,
+            Energy::Down => Energy::Left,
+            Energy::Right => Energy::Up,
+            Energy::Left => Energy::Down,
+        }],
+        '\\' => vec![match energy {
+            Energy::Up => Energy::Left,
+            Energy::Down => Energy::Right,
+            Energy::Right => Energy::Down,
+            Energy::Left => Energy::Up,
+        }],
+        '|' => match energy {
+            Energy::Up => vec![Energy::Up],
+            Energy::Down => vec![Energy::Down],
+            Energy::Right | Energy::Left => vec![Energy::Up, Energy::Down],
+        },
+        '-' => match energy {
+            Energy::Left => vec![Energy::Left],
+            Energy::Right => vec![Energy::Right],
+            Energy::Up | Energy::Down => vec![Energy::Left, Energy::Right],
+        },
+        _ => unimplemented!(),
+    }
+}
+
+impl Map {
+    fn next_states(
+        &self,
+        (row, col, dir): (usize, usize, Energy),
+    ) -> impl Iterator<Item = (usize, usize, Energy)> + '_ {
+        next_energy_options(self.0[(row, col)], dir)
+            .into_iter()
+            .filter_map(move |energy| {
+                let (row, col) = self.0.offset((row, col), energy_to_incr(energy))?;
+                Some((row, col, energy))
+            })
+    }
+
+    fn traverse_map(&self, initial_state: (usize, usize, Energy)) -> EnergyMap {
+        fn inner(map: &Map, energy: &mut EnergyMap, state: (usize, usize, Energy)) {
+            if energy.insert(state) {
+                // This path and direction has already been explored
+                return;
+            }
+
+            for s in map.next_states(state) {
+                inner(map, energy, s);
+            }
+        }
+
+        let mut energy = EnergyMap::new(self.0.rows(), self.0.cols());
+        inner(self, &mut energy, initial_state);
+        energy
+    }
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = Map;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(Map(Grid::parse(input, ".|-/\\")?))
+    }
+
+    fn part1(map: &Self::Input) -> Answer {
+        map.traverse_map((0, 0, Energy::Right)).energized().into()
+    }
+
+    fn part2(map: &Self::Input) -> Answer {
+        let rows = map.0.rows();
+        let cols = map.0.cols();
+
+        let initial_states = (0..rows)
+            .map(|r| (r, 0, Energy::Right))
+            .chain((0..rows).map(|r| (r, cols - 1, Energy::Left)))
+            .chain((0..cols).map(|c| (0, c, Energy::Down)))
+            .chain((0..cols).map(|c| (rows - 1, c, Energy::Up)));
+
+        let energy_maps = initial_states.map(|initial_state| map.traverse_map(initial_state));
+
+        energy_maps
+            .map(|energy_map| energy_map.energized())
+            .max()
+            .unwrap()
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = r".|...\....
+|.-.\.....
+.....|-...
+........|.
+..........
+.........\
+..../.\\..
+.-.-/..|..
+.|....-|.\
+..//.|....
+";
+
+    #[test]
+    fn solves_example() {
+        let input = Day16::parse(EXAMPLE).unwrap();
+        assert_eq!(Day16::part1(&input), Answer::from(46_usize));
+        assert_eq!(Day16::part2(&input), Answer::from(51_usize));
+    }
+}
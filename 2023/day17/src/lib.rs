@@ -0,0 +1,306 @@
+use common::parse;
+use common::search;
+use common::{Answer, Grid, ParseError, Pos, Solution};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    fn opposite(&self) -> Self {
+        match self {
+            Direction::Down => Direction::Up,
+            Direction::Up => Direction::Down,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+
+    fn arrow(&self) -> char {
+        match self {
+            Direction::Up => '^',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+            Direction::Right => '>',
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
+struct Trace {
+    direction: Option<Direction>,
+    consecutive: usize,
+}
+
+impl Trace {
+    fn new() -> Self {
+        Self {
+            direction: None,
+            consecutive: 0,
+        }
+    }
+
+    fn with_movement(&self, dir: Direction) -> Self {
+        if self.direction == Some(dir) {
+            Self {
+                consecutive: self.consecutive + 1,
+                direction: self.direction,
+            }
+        } else {
+            Self {
+                consecutive: 1,
+                direction: Some(dir),
+            }
+        }
+    }
+}
+
+fn dir_to_incr(dir: Direction) -> (isize, isize) {
+    match dir {
+        Direction::Up => (-1, 0),
+        Direction::Down => (1, 0),
+        Direction::Left => (0, -1),
+        Direction::Right => (0, 1),
+    }
+}
+
+/// Straight-run limits of a crucible: it must move at least `min_consecutive` blocks before
+/// turning or stopping, and at most `max_consecutive` before turning.
+#[derive(Debug, Clone, Copy)]
+pub struct Crucible {
+    pub min_consecutive: usize,
+    pub max_consecutive: usize,
+}
+
+pub const CRUCIBLE: Crucible = Crucible {
+    min_consecutive: 1,
+    max_consecutive: 3,
+};
+
+pub const ULTRA_CRUCIBLE: Crucible = Crucible {
+    min_consecutive: 4,
+    max_consecutive: 10,
+};
+
+impl Crucible {
+    fn can_move(&self, trace: &Trace, dir: Direction) -> bool {
+        let backwards = trace.direction.is_some_and(|d| d.opposite() == dir);
+        let too_many_consecutive = trace.with_movement(dir).consecutive > self.max_consecutive;
+        let too_little_consecutive =
+            trace.consecutive < self.min_consecutive && trace.direction.is_some_and(|d| d != dir);
+
+        !backwards && !too_many_consecutive && !too_little_consecutive
+    }
+
+    fn can_stop(&self, trace: &Trace) -> bool {
+        trace.consecutive >= self.min_consecutive
+    }
+
+    // Moves from `pos` along with the heat lost entering the next block
+    fn moves<'a>(
+        &'a self,
+        map: &'a Grid<usize>,
+        (pos, trace): (Pos, Trace),
+    ) -> impl Iterator<Item = ((Pos, Trace), usize)> + 'a {
+        const DIRS: [Direction; 4] = [
+            Direction::Up,
+            Direction::Down,
+            Direction::Left,
+            Direction::Right,
+        ];
+
+        DIRS.into_iter()
+            .filter(move |dir| self.can_move(&trace, *dir))
+            .filter_map(move |dir| {
+                let next = map.offset(pos, dir_to_incr(dir))?;
+                Some(((next, trace.with_movement(dir)), map[next]))
+            })
+    }
+}
+
+/// Route taken by a crucible, as the block entered by every move and the direction of the move.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route {
+    pub heat_loss: usize,
+    pub moves: Vec<(usize, usize, Direction)>,
+}
+
+fn direction_between((r0, c0): Pos, (r1, c1): Pos) -> Direction {
+    match (r1 as isize - r0 as isize, c1 as isize - c0 as isize) {
+        (-1, 0) => Direction::Up,
+        (1, 0) => Direction::Down,
+        (0, -1) => Direction::Left,
+        (0, 1) => Direction::Right,
+        d => panic!(
+            "Blocks {:?} and {:?} are not adjacent: {d:?}",
+            (r0, c0),
+            (r1, c1)
+        ),
+    }
+}
+
+/// Cheapest route to the bottom right block, if the crucible limits allow reaching it.
+pub fn shortest_path(crucible: &Crucible, entry: Pos, map: &Grid<usize>) -> Option<Route> {
+    let end = map.max();
+
+    // Every block costs at least 1, so the manhattan distance never overestimates
+    let path = search::astar(
+        (entry, Trace::new()),
+        |state| crucible.moves(map, *state),
+        |(pos, trace)| *pos == end && crucible.can_stop(trace),
+        |(pos, _)| pos.0.abs_diff(end.0) + pos.1.abs_diff(end.1),
+    )?;
+
+    let moves = path
+        .states
+        .windows(2)
+        .map(|w| {
+            let (from, (to, _)) = (w[0].0, w[1]);
+            (to.0, to.1, direction_between(from, to))
+        })
+        .collect();
+
+    Some(Route {
+        heat_loss: path.cost,
+        moves,
+    })
+}
+
+/// Draws the route over the heat loss map the same way the puzzle text does.
+pub fn render_route(map: &Grid<usize>, route: &Route) -> String {
+    let mut overlay = map.map(|heat_loss| char::from_digit(*heat_loss as u32, 10).unwrap());
+    for (row, col, dir) in &route.moves {
+        overlay[(*row, *col)] = dir.arrow();
+    }
+    overlay.to_string()
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = Grid<usize>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let map = Grid::parse_with(input, |cell| match parse::number(cell)? {
+            0 => Err(ParseError::new(cell, "a heat loss between 1 and 9")),
+            heat_loss => Ok(heat_loss),
+        })?;
+        if map.rows() == 0 || map.cols() == 0 {
+            return Err(ParseError::after(input, "a heat loss map"));
+        }
+        Ok(map)
+    }
+
+    fn part1(map: &Self::Input) -> Answer {
+        match shortest_path(&CRUCIBLE, (0, 0), map) {
+            Some(route) => route.heat_loss.into(),
+            None => "the crucible cannot reach the factory".to_owned().into(),
+        }
+    }
+
+    fn part2(map: &Self::Input) -> Answer {
+        match shortest_path(&ULTRA_CRUCIBLE, (0, 0), map) {
+            Some(route) => route.heat_loss.into(),
+            None => "the crucible cannot reach the factory".to_owned().into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "2413432311323
+3215453535623
+3255245654254
+3446585845452
+4546657867536
+1438598798454
+4457876987766
+3637877979653
+4654967986887
+4564679986453
+1224686865563
+2546548887735
+4322674655533
+";
+
+    #[test]
+    fn solves_example() {
+        let input = Day17::parse(EXAMPLE).unwrap();
+        assert_eq!(Day17::part1(&input), Answer::from(102_usize));
+        assert_eq!(Day17::part2(&input), Answer::from(94_usize));
+    }
+
+    #[test]
+    fn unreachable_factory() {
+        // The ultra crucible must move 4 blocks before it can stop
+        let input = Day17::parse("1\n2\n").unwrap();
+        assert_eq!(Day17::part1(&input), Answer::from(2_usize));
+        assert_eq!(
+            Day17::part2(&input),
+            Answer::from("the crucible cannot reach the factory".to_owned())
+        );
+    }
+
+    #[test]
+    fn renders_route() {
+        let map = Day17::parse("111\n991\n991\n").unwrap();
+        let route = shortest_path(&CRUCIBLE, (0, 0), &map).unwrap();
+
+        assert_eq!(route.heat_loss, 4);
+        assert_eq!(
+            route.moves,
+            [
+                (0, 1, Direction::Right),
+                (0, 2, Direction::Right),
+                (1, 2, Direction::Down),
+                (2, 2, Direction::Down),
+            ]
+        );
+        assert_eq!(render_route(&map, &route), "1>>\n99v\n99v\n");
+    }
+
+    #[test]
+    fn trace_counts_straight_runs() {
+        let trace = Trace::new().with_movement(Direction::Down);
+        assert_eq!(trace.direction, Some(Direction::Down));
+        assert_eq!(trace.consecutive, 1);
+
+        let trace = trace.with_movement(Direction::Down);
+        assert_eq!(trace.consecutive, 2);
+
+        let trace = trace.with_movement(Direction::Right);
+        assert_eq!(trace.direction, Some(Direction::Right));
+        assert_eq!(trace.consecutive, 1);
+    }
+
+    fn straight(dir: Direction, blocks: usize) -> Trace {
+        (0..blocks).fold(Trace::new(), |t, _| t.with_movement(dir))
+    }
+
+    #[test]
+    fn crucible_limits() {
+        let right = |blocks| straight(Direction::Right, blocks);
+
+        assert!(CRUCIBLE.can_move(&right(2), Direction::Right));
+        assert!(!CRUCIBLE.can_move(&right(3), Direction::Right));
+        assert!(CRUCIBLE.can_move(&right(1), Direction::Down));
+        assert!(!CRUCIBLE.can_move(&right(1), Direction::Left));
+
+        assert!(!ULTRA_CRUCIBLE.can_move(&right(3), Direction::Down));
+        assert!(ULTRA_CRUCIBLE.can_move(&right(4), Direction::Down));
+        assert!(ULTRA_CRUCIBLE.can_move(&right(9), Direction::Right));
+        assert!(!ULTRA_CRUCIBLE.can_move(&right(10), Direction::Right));
+        assert!(!ULTRA_CRUCIBLE.can_stop(&right(3)));
+        assert!(ULTRA_CRUCIBLE.can_stop(&right(4)));
+
+        // The first move counts towards the run, even when it goes down
+        let down = straight(Direction::Down, 1);
+        assert!(!ULTRA_CRUCIBLE.can_move(&down, Direction::Right));
+    }
+}
@@ -0,0 +1,242 @@
+use std::collections::HashMap;
+
+use common::parse;
+use common::{Answer, ParseError, Solution};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    fn opposite(&self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Instruction {
+    direction: Direction,
+    amount: usize,
+}
+
+pub struct Step {
+    plan: Instruction,
+    color: Instruction,
+}
+
+fn parse_plan_direction(dir: &str) -> Result<Direction, ParseError> {
+    match dir {
+        "U" => Ok(Direction::Up),
+        "D" => Ok(Direction::Down),
+        "L" => Ok(Direction::Left),
+        "R" => Ok(Direction::Right),
+        d => Err(ParseError::new(d, "one of U, D, L or R")),
+    }
+}
+
+fn parse_direction(dir: &str) -> Result<Direction, ParseError> {
+    match dir {
+        "3" => Ok(Direction::Up),
+        "1" => Ok(Direction::Down),
+        "2" => Ok(Direction::Left),
+        "0" => Ok(Direction::Right),
+        d => Err(ParseError::new(d, "a direction digit between 0 and 3")),
+    }
+}
+
+fn parse_line(l: &str) -> Result<Step, ParseError> {
+    let mut iter = l.split_ascii_whitespace();
+    let plan_direction = parse_plan_direction(parse::next(&mut iter, l, "a direction")?)?;
+    let plan_amount = parse::number(parse::next(&mut iter, l, "a distance")?)?;
+    let color = parse::next(&mut iter, l, "a color")?;
+    let mixed = parse::strip_prefix(color, "(#")?;
+    let mixed = parse::strip_suffix(mixed, ")")?;
+    if mixed.len() != 6 || !mixed.is_char_boundary(5) {
+        return Err(ParseError::new(mixed, "6 hexadecimal digits"));
+    }
+    let amount = usize::from_str_radix(&mixed[..5], 16)
+        .map_err(|_| ParseError::new(&mixed[..5], "a hexadecimal distance"))?;
+    let direction = parse_direction(&mixed[5..])?;
+
+    Ok(Step {
+        plan: Instruction {
+            direction: plan_direction,
+            amount: plan_amount,
+        },
+        color: Instruction { direction, amount },
+    })
+}
+
+type Coord = (isize, isize);
+
+fn walk((row, col): Coord, dir: Direction) -> Coord {
+    match dir {
+        Direction::Up => (row - 1, col),
+        Direction::Down => (row + 1, col),
+        Direction::Left => (row, col - 1),
+        Direction::Right => (row, col + 1),
+    }
+}
+
+struct Map(HashMap<isize, Vec<(isize, Direction, Direction)>>);
+
+impl Map {
+    fn new() -> Self {
+        Self(HashMap::new())
+    }
+
+    fn insert(&mut self, (row, col): Coord, prev_dir: Direction, next_dir: Direction) {
+        if let Some(row) = self.0.get_mut(&row) {
+            row.push((col, prev_dir, next_dir));
+        } else {
+            self.0.insert(row, vec![(col, prev_dir, next_dir)]);
+        }
+    }
+}
+
+fn follow_instructions(insns: &[Instruction]) -> Map {
+    let mut result = Map::new();
+
+    let mut current = (0, 0);
+
+    let cur = insns.iter();
+    let next = insns.iter().skip(1).chain(std::iter::once(&insns[0]));
+
+    for (cur_insn, next_insn) in cur.zip(next) {
+        assert!(cur_insn.amount >= 1);
+
+        for _i in 0..cur_insn.amount - 1 {
+            let next = walk(current, cur_insn.direction);
+            result.insert(next, cur_insn.direction, cur_insn.direction);
+            current = next;
+        }
+
+        let next = walk(current, cur_insn.direction);
+        result.insert(next, cur_insn.direction, next_insn.direction);
+        current = next;
+    }
+    assert_eq!(current, (0, 0));
+
+    result
+}
+
+fn area(map: &Map) -> usize {
+    let mut count = 0usize;
+    for (_row, cols) in map.0.iter() {
+        let mut cols = cols.clone();
+        cols.sort_by_key(|(col, _, _)| *col);
+
+        let mut inner = false;
+        let mut last = None;
+        let mut first_edge = None;
+
+        for (col, prev_dir, next_dir) in cols {
+            count += 1;
+
+            if let Some(last) = last {
+                assert!(col > last);
+                if last + 1 < col && inner {
+                    count += (col - last - 1) as usize;
+                }
+            }
+
+            if prev_dir != next_dir {
+                if next_dir == Direction::Right {
+                    assert!(first_edge.is_none());
+                    first_edge = Some(prev_dir);
+                } else if prev_dir == Direction::Left {
+                    assert!(first_edge.is_none());
+                    first_edge = Some(next_dir);
+                } else if prev_dir == Direction::Right {
+                    assert!(first_edge.is_some());
+                    if next_dir == first_edge.unwrap() {
+                        inner = !inner;
+                    }
+                    first_edge = None;
+                } else if next_dir == Direction::Left {
+                    assert!(first_edge.is_some());
+                    if prev_dir == first_edge.unwrap() {
+                        inner = !inner;
+                    }
+                    first_edge = None;
+                }
+            } else if prev_dir == Direction::Up || prev_dir == Direction::Down {
+                assert!(first_edge.is_none());
+                inner = !inner;
+            }
+
+            last = Some(col);
+        }
+    }
+
+    count
+}
+
+fn dig(instructions: &[Instruction]) -> usize {
+    let mut last = instructions.first().unwrap();
+    for i in instructions.iter().skip(1) {
+        // This solution only works if there are no sudden changes in opposite directions
+        assert!(i.direction != last.direction.opposite());
+        last = i;
+    }
+
+    let map = follow_instructions(instructions);
+    area(&map)
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = Vec<Step>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.lines().map(parse_line).collect()
+    }
+
+    fn part1(steps: &Self::Input) -> Answer {
+        let instructions: Vec<_> = steps.iter().map(|s| s.plan.clone()).collect();
+        dig(&instructions).into()
+    }
+
+    fn part2(steps: &Self::Input) -> Answer {
+        let instructions: Vec<_> = steps.iter().map(|s| s.color.clone()).collect();
+        dig(&instructions).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "R 6 (#70c710)
+D 5 (#0dc571)
+L 2 (#5713f0)
+D 2 (#d2c081)
+R 2 (#59c680)
+D 2 (#411b91)
+L 5 (#8ceee2)
+U 2 (#caa173)
+L 1 (#1b58a2)
+U 2 (#caa171)
+R 2 (#7807d2)
+U 3 (#a77fa3)
+L 2 (#015232)
+U 2 (#7a21e3)
+";
+
+    #[test]
+    fn solves_example() {
+        let input = Day18::parse(EXAMPLE).unwrap();
+        assert_eq!(Day18::part1(&input), Answer::from(62_usize));
+        assert_eq!(Day18::part2(&input), Answer::from(952408144115_usize));
+    }
+}
@@ -0,0 +1,969 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::ops::Range;
+
+use common::parse;
+use common::{Answer, IntervalSet, ParseError, Solution};
+use serde::Serialize;
+
+// Values an attribute can take when counting combinations
+const RATINGS: Range<usize> = 1..4001;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Condition {
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Equal,
+    NotEqual,
+}
+
+impl Condition {
+    fn operator(self) -> &'static str {
+        OPERATORS
+            .iter()
+            .find(|(_, cond)| *cond == self)
+            .map(|(op, _)| *op)
+            .expect("Every condition has an operator")
+    }
+
+    fn holds(self, value: usize, threshold: usize) -> bool {
+        match self {
+            Condition::Less => value < threshold,
+            Condition::LessOrEqual => value <= threshold,
+            Condition::Greater => value > threshold,
+            Condition::GreaterOrEqual => value >= threshold,
+            Condition::Equal => value == threshold,
+            Condition::NotEqual => value != threshold,
+        }
+    }
+}
+
+// Longer operators first so that `<=` isn't read as `<`
+const OPERATORS: [(&str, Condition); 6] = [
+    ("<=", Condition::LessOrEqual),
+    (">=", Condition::GreaterOrEqual),
+    ("==", Condition::Equal),
+    ("!=", Condition::NotEqual),
+    ("<", Condition::Less),
+    (">", Condition::Greater),
+];
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum RuleResult {
+    Accepted,
+    Rejected,
+    Workflow(String),
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct Rule {
+    condition: Option<(String, Condition, usize)>,
+    result: RuleResult,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Workflow(Vec<Rule>);
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum WorkflowResult {
+    Accepted,
+    Rejected,
+}
+
+/// Ratings of a part, by attribute name.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Item(BTreeMap<String, usize>);
+
+impl Item {
+    fn get_var(&self, var: &str) -> usize {
+        self.0[var]
+    }
+
+    fn total(&self) -> usize {
+        self.0.values().sum()
+    }
+}
+
+fn parse_var(var: &str) -> Result<&str, ParseError> {
+    if var.is_empty() || !var.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(ParseError::new(var, "an attribute name"));
+    }
+    Ok(var)
+}
+
+fn parse_condition(cond: &str) -> Result<(String, Condition, usize), ParseError> {
+    let Some(pos) = cond.find(['<', '>', '=', '!']) else {
+        return Err(ParseError::after(cond, "a comparison"));
+    };
+    let var = parse_var(&cond[..pos])?;
+    let rest = &cond[pos..];
+    let Some((op, cond_type)) = OPERATORS.iter().find(|(op, _)| rest.starts_with(op)) else {
+        return Err(ParseError::new(&rest[..1], "one of <, >, <=, >=, == or !="));
+    };
+
+    Ok((
+        var.to_owned(),
+        *cond_type,
+        parse::number(&rest[op.len()..])?,
+    ))
+}
+
+fn parse_result(cond: &str) -> Result<RuleResult, ParseError> {
+    match cond {
+        "A" => Ok(RuleResult::Accepted),
+        "R" => Ok(RuleResult::Rejected),
+        "" => Err(ParseError::new(cond, "a workflow name")),
+        w => Ok(RuleResult::Workflow(w.to_owned())),
+    }
+}
+
+fn parse_workflow(line: &str) -> Result<(String, Workflow), ParseError> {
+    let (name, rules) = parse::split_once(line, "{")?;
+    if name.is_empty() {
+        return Err(ParseError::new(name, "a workflow name"));
+    }
+    let rules = parse::strip_suffix(rules, "}")?;
+    let w = Workflow(
+        rules
+            .split(',')
+            .map(|rule| {
+                if let Some((condition, result)) = rule.split_once(':') {
+                    let condition = Some(parse_condition(condition)?);
+                    let result = parse_result(result)?;
+                    Ok(Rule { condition, result })
+                } else {
+                    let result = parse_result(rule)?;
+                    Ok(Rule {
+                        condition: None,
+                        result,
+                    })
+                }
+            })
+            .collect::<Result<_, ParseError>>()?,
+    );
+
+    Ok((name.to_owned(), w))
+}
+
+fn parse_item(line: &str) -> Result<Item, ParseError> {
+    let ratings = parse::strip_prefix(line, "{")?;
+    let ratings = parse::strip_suffix(ratings, "}")?;
+    let mut item = BTreeMap::new();
+    for rating in ratings.split(',') {
+        let (name, value) = parse::split_once(rating, "=")?;
+        let name = parse_var(name)?;
+        let rating = parse::number(value)?;
+        // Validation only follows ratings in that range through the workflows
+        if !RATINGS.contains(&rating) {
+            return Err(ParseError::new(
+                value,
+                format!("a rating from {} to {}", RATINGS.start, RATINGS.end - 1),
+            ));
+        }
+        if item.insert(name.to_owned(), rating).is_some() {
+            return Err(ParseError::new(name, format!("a single rating for {name}")));
+        }
+    }
+    Ok(Item(item))
+}
+
+fn apply_workflows(part: &Item, workflows: &HashMap<String, Workflow>) -> WorkflowResult {
+    let mut c = "in";
+
+    loop {
+        let w = &workflows[c];
+        for rule in &w.0 {
+            let apply_result = if let Some((var, cond, threshold)) = &rule.condition {
+                cond.holds(part.get_var(var), *threshold)
+            } else {
+                true
+            };
+
+            if apply_result {
+                match &rule.result {
+                    RuleResult::Accepted => return WorkflowResult::Accepted,
+                    RuleResult::Rejected => return WorkflowResult::Rejected,
+                    RuleResult::Workflow(next_w) => c = next_w,
+                };
+                break;
+            }
+        }
+    }
+}
+
+/// Ratings of a set of parts, by attribute name.
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct ItemRange(BTreeMap<String, IntervalSet<usize>>);
+
+// Values satisfying `cond` against `threshold`. Ratings stay below usize::MAX, so a threshold
+// there leaves nothing above it
+fn matching(cond: Condition, threshold: usize) -> IntervalSet<usize> {
+    match cond {
+        Condition::Greater => IntervalSet::from(threshold.saturating_add(1)..usize::MAX),
+        Condition::GreaterOrEqual => IntervalSet::from(threshold..usize::MAX),
+        Condition::Less => IntervalSet::from(0..threshold),
+        Condition::LessOrEqual => IntervalSet::from(0..threshold.saturating_add(1)),
+        Condition::Equal => IntervalSet::from(threshold..threshold.saturating_add(1)),
+        Condition::NotEqual => {
+            IntervalSet::from(0..usize::MAX).difference(&matching(Condition::Equal, threshold))
+        }
+    }
+}
+
+// Ratings are listed as inclusive bounds, which is how the rules are written
+impl std::fmt::Display for ItemRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, (var, range)) in self.0.iter().enumerate() {
+            let bounds: Vec<_> = range
+                .ranges()
+                .iter()
+                .map(|r| format!("{}..={}", r.start, r.end - 1))
+                .collect();
+            let sep = if i == 0 { "" } else { " " };
+            write!(f, "{sep}{var}={}", bounds.join(","))?;
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for ItemRange {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.0.iter().map(|(var, range)| {
+            let bounds: Vec<_> = range
+                .ranges()
+                .iter()
+                .map(|r| [r.start, r.end - 1])
+                .collect();
+            (var, bounds)
+        }))
+    }
+}
+
+impl ItemRange {
+    fn new<'a>(attributes: impl IntoIterator<Item = &'a String>) -> Self {
+        Self(
+            attributes
+                .into_iter()
+                .map(|var| (var.clone(), IntervalSet::from(RATINGS)))
+                .collect(),
+        )
+    }
+
+    fn combinations(&self) -> usize {
+        self.0.values().map(|range| range.len()).product()
+    }
+
+    fn get_var_range(&self, var: &str) -> &IntervalSet<usize> {
+        &self.0[var]
+    }
+
+    fn with_var_range(&self, var: &str, range: IntervalSet<usize>) -> Option<ItemRange> {
+        if range.is_empty() {
+            return None;
+        }
+
+        let mut cloned = self.clone();
+        cloned.0.insert(var.to_owned(), range);
+        Some(cloned)
+    }
+
+    fn with_constraint(&self, var: &str, cond: Condition, threshold: usize) -> Option<ItemRange> {
+        let range = self
+            .get_var_range(var)
+            .intersection(&matching(cond, threshold));
+        self.with_var_range(var, range)
+    }
+
+    fn with_opposite_constraint(
+        &self,
+        var: &str,
+        cond: Condition,
+        threshold: usize,
+    ) -> Option<ItemRange> {
+        let range = self
+            .get_var_range(var)
+            .difference(&matching(cond, threshold));
+        self.with_var_range(var, range)
+    }
+}
+
+/// Step of the way from `in` to an accepted region: rule `rule` (0-based) of `workflow` sent the
+/// parts to `target`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize)]
+pub struct Step {
+    pub workflow: String,
+    pub rule: usize,
+    /// `None` for the unconditional rule ending a workflow
+    pub condition: Option<String>,
+    pub target: String,
+}
+
+/// Block of parts accepted by the workflows, along with the rules that lead to it.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct Region {
+    path: Vec<Step>,
+    ratings: ItemRange,
+    combinations: usize,
+}
+
+impl Region {
+    pub fn path(&self) -> &[Step] {
+        &self.path
+    }
+
+    pub fn combinations(&self) -> usize {
+        self.combinations
+    }
+}
+
+pub fn discover_combinations(system: &System) -> Vec<Region> {
+    fn run_wflow_impl(
+        mut cur: ItemRange,
+        workflow: &str,
+        workflows: &HashMap<String, Workflow>,
+        path: &mut Vec<Step>,
+        regions: &mut Vec<Region>,
+    ) {
+        let mut send = |range: ItemRange, rule: usize, path: &mut Vec<Step>| {
+            let Rule { condition, result } = &workflows[workflow].0[rule];
+            let target = match result {
+                RuleResult::Accepted => "A",
+                RuleResult::Rejected => return,
+                RuleResult::Workflow(other) => other,
+            };
+            path.push(Step {
+                workflow: workflow.to_owned(),
+                rule,
+                condition: condition
+                    .as_ref()
+                    .map(|(var, cond, threshold)| format!("{var}{}{threshold}", cond.operator())),
+                target: target.to_owned(),
+            });
+            match result {
+                RuleResult::Workflow(other) => {
+                    run_wflow_impl(range, other, workflows, path, regions)
+                }
+                _ => regions.push(Region {
+                    path: path.clone(),
+                    combinations: range.combinations(),
+                    ratings: range,
+                }),
+            }
+            path.pop();
+        };
+
+        for (idx, rule) in workflows[workflow].0.iter().enumerate() {
+            if let Some((var, cond, threshold)) = &rule.condition {
+                if let Some(new_range) = cur.with_constraint(var, *cond, *threshold) {
+                    send(new_range, idx, path);
+                }
+                match cur.with_opposite_constraint(var, *cond, *threshold) {
+                    Some(new_range) => cur = new_range,
+                    // Every remaining part was sent elsewhere by this rule
+                    None => return,
+                }
+            } else {
+                send(cur, idx, path);
+                return;
+            }
+        }
+    }
+
+    let mut regions = vec![];
+    run_wflow_impl(
+        ItemRange::new(&system.attributes),
+        "in",
+        &system.workflows,
+        &mut vec![],
+        &mut regions,
+    );
+    regions
+}
+
+/// Accepted regions and their total number of combinations, as pretty-printed JSON.
+pub fn to_json(regions: &[Region]) -> String {
+    let total: usize = regions.iter().map(|r| r.combinations).sum();
+    serde_json::to_string_pretty(&serde_json::json!({
+        "combinations": total,
+        "regions": regions,
+    }))
+    .expect("Regions are plain data")
+}
+
+// Escapes text put between double quotes in a dot file
+fn quote(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Graphviz tree of the rules leading to the accepted regions. Each node is a workflow reached by
+/// some block of parts, so a workflow shows up once per way of getting to it.
+pub fn to_dot(regions: &[Region]) -> String {
+    let mut out =
+        String::from("digraph workflows {\n    node [shape=box];\n    n0 [label=\"in\"];\n");
+    let mut nodes: HashMap<&[Step], usize> = HashMap::from([(&[][..], 0)]);
+
+    for region in regions {
+        for len in 1..=region.path.len() {
+            let prefix = &region.path[..len];
+            if nodes.contains_key(prefix) {
+                continue;
+            }
+            let id = nodes.len();
+            let parent = nodes[&prefix[..len - 1]];
+            nodes.insert(prefix, id);
+
+            let step = &prefix[len - 1];
+            let label = if len == region.path.len() {
+                let lines = [
+                    "A".to_owned(),
+                    region.ratings.to_string(),
+                    format!("{} combinations", region.combinations),
+                ];
+                lines.map(|l| quote(&l)).join("\\n")
+            } else {
+                quote(&step.target)
+            };
+            let edge = match &step.condition {
+                Some(condition) => format!("#{} {condition}", step.rule + 1),
+                None => format!("#{} otherwise", step.rule + 1),
+            };
+            out += &format!("    n{id} [label=\"{label}\"];\n");
+            out += &format!("    n{parent} -> n{id} [label=\"{}\"];\n", quote(&edge));
+        }
+    }
+    out += "}\n";
+    out
+}
+
+/// Problem found by [`validate`] in a set of workflows.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Issue {
+    /// There is no `in` workflow to start from
+    MissingStart,
+    /// Rule `rule` (0-based) of `workflow` sends parts to a workflow that doesn't exist
+    UndefinedWorkflow {
+        workflow: String,
+        rule: usize,
+        target: String,
+    },
+    /// Some parts go around these workflows forever, the first one is repeated at the end
+    Cycle(Vec<String>),
+    /// Some parts reaching `workflow` match none of its rules
+    FallThrough(String),
+    /// Rule `rule` (0-based) of `workflow` comes after an unconditional rule and never applies
+    ShadowedRule { workflow: String, rule: usize },
+    /// No part is ever sent to the workflow
+    Unreachable(String),
+}
+
+impl Issue {
+    /// Whether evaluating the workflows could panic or never end, other issues are harmless.
+    pub fn is_error(&self) -> bool {
+        !matches!(self, Issue::ShadowedRule { .. } | Issue::Unreachable(_))
+    }
+
+    /// Workflow the issue is about.
+    pub fn workflow(&self) -> Option<&str> {
+        match self {
+            Issue::MissingStart => None,
+            Issue::UndefinedWorkflow { workflow, .. } | Issue::ShadowedRule { workflow, .. } => {
+                Some(workflow)
+            }
+            Issue::Cycle(workflows) => workflows.first().map(|w| w.as_str()),
+            Issue::FallThrough(workflow) | Issue::Unreachable(workflow) => Some(workflow),
+        }
+    }
+}
+
+impl std::fmt::Display for Issue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Issue::MissingStart => write!(f, "no workflow named in"),
+            Issue::UndefinedWorkflow {
+                workflow,
+                rule,
+                target,
+            } => write!(
+                f,
+                "rule {} of {workflow} sends parts to undefined workflow {target}",
+                rule + 1
+            ),
+            Issue::Cycle(workflows) => write!(f, "parts loop through {}", workflows.join(" -> ")),
+            Issue::FallThrough(workflow) => {
+                write!(f, "some parts match none of the rules of {workflow}")
+            }
+            Issue::ShadowedRule { workflow, rule } => write!(
+                f,
+                "rule {} of {workflow} follows an unconditional rule",
+                rule + 1
+            ),
+            Issue::Unreachable(workflow) => write!(f, "no part is ever sent to {workflow}"),
+        }
+    }
+}
+
+/// Checks the workflows without evaluating any part. References and shadowed rules are checked
+/// on every workflow, loops and missing fallbacks only where some range of parts can get to.
+pub fn validate(system: &System) -> Vec<Issue> {
+    fn explore(
+        mut cur: ItemRange,
+        name: &str,
+        workflows: &HashMap<String, Workflow>,
+        stack: &mut Vec<String>,
+        reached: &mut HashSet<String>,
+        issues: &mut Vec<Issue>,
+    ) {
+        if let Some(pos) = stack.iter().position(|w| w == name) {
+            let mut cycle = stack[pos..].to_vec();
+            cycle.push(name.to_owned());
+            let issue = Issue::Cycle(cycle);
+            if !issues.contains(&issue) {
+                issues.push(issue);
+            }
+            return;
+        }
+        // Undefined targets are reported for every rule below
+        let Some(workflow) = workflows.get(name) else {
+            return;
+        };
+
+        reached.insert(name.to_owned());
+        stack.push(name.to_owned());
+        let mut send = |range: ItemRange, result: &RuleResult, issues: &mut Vec<Issue>| {
+            if let RuleResult::Workflow(other) = result {
+                explore(range, other, workflows, stack, reached, issues);
+            }
+        };
+
+        let mut remaining = true;
+        for rule in &workflow.0 {
+            let Some((var, cond, threshold)) = &rule.condition else {
+                send(cur.clone(), &rule.result, issues);
+                remaining = false;
+                break;
+            };
+            if let Some(new_range) = cur.with_constraint(var, *cond, *threshold) {
+                send(new_range, &rule.result, issues);
+            }
+            match cur.with_opposite_constraint(var, *cond, *threshold) {
+                Some(new_range) => cur = new_range,
+                None => {
+                    remaining = false;
+                    break;
+                }
+            }
+        }
+        if remaining {
+            let issue = Issue::FallThrough(name.to_owned());
+            if !issues.contains(&issue) {
+                issues.push(issue);
+            }
+        }
+        stack.pop();
+    }
+
+    let mut issues = vec![];
+    let mut names: Vec<_> = system.workflows.keys().collect();
+    names.sort();
+
+    for name in &names {
+        let rules = &system.workflows[*name].0;
+        for (idx, rule) in rules.iter().enumerate() {
+            if let RuleResult::Workflow(target) = &rule.result {
+                if !system.workflows.contains_key(target) {
+                    issues.push(Issue::UndefinedWorkflow {
+                        workflow: name.to_string(),
+                        rule: idx,
+                        target: target.clone(),
+                    });
+                }
+            }
+        }
+        if let Some(first) = rules.iter().position(|rule| rule.condition.is_none()) {
+            issues.extend((first + 1..rules.len()).map(|rule| Issue::ShadowedRule {
+                workflow: name.to_string(),
+                rule,
+            }));
+        }
+    }
+
+    if !system.workflows.contains_key("in") {
+        issues.insert(0, Issue::MissingStart);
+        return issues;
+    }
+
+    let mut reached = HashSet::new();
+    explore(
+        ItemRange::new(&system.attributes),
+        "in",
+        &system.workflows,
+        &mut vec![],
+        &mut reached,
+        &mut issues,
+    );
+    issues.extend(
+        names
+            .iter()
+            .filter(|name| !reached.contains(**name))
+            .map(|name| Issue::Unreachable(name.to_string())),
+    );
+    issues
+}
+
+#[derive(Debug)]
+pub struct System {
+    workflows: HashMap<String, Workflow>,
+    items: Vec<Item>,
+    /// Every attribute named by a rule or an item.
+    attributes: BTreeSet<String>,
+}
+
+/// Parses the workflows and items without checking that the workflows can be evaluated.
+pub fn parse(input: &str) -> Result<System, ParseError> {
+    let mut workflows = HashMap::new();
+    let mut items = vec![];
+    let mut attributes = BTreeSet::new();
+    let mut found_newline = false;
+
+    for line in input.lines() {
+        if line.trim().is_empty() {
+            found_newline = true;
+            continue;
+        }
+
+        if !found_newline {
+            let (name, w) = parse_workflow(line)?;
+            if workflows.contains_key(&name) {
+                return Err(ParseError::new(
+                    &line[..name.len()],
+                    format!("a single workflow named {name}"),
+                ));
+            }
+            for rule in &w.0 {
+                if let Some((var, _, _)) = &rule.condition {
+                    attributes.insert(var.clone());
+                }
+            }
+            workflows.insert(name, w);
+        } else {
+            let item = parse_item(line)?;
+            // Workflows all come before the items, so every tested attribute is known here
+            if let Some(var) = attributes.iter().find(|var| !item.0.contains_key(*var)) {
+                return Err(ParseError::new(line, format!("a rating for {var}")));
+            }
+            attributes.extend(item.0.keys().cloned());
+            items.push(item);
+        }
+    }
+
+    Ok(System {
+        workflows,
+        items,
+        attributes,
+    })
+}
+
+// Line defining workflow `name`, workflows are defined on a line of their own starting with it
+fn workflow_line<'a>(input: &'a str, name: &str) -> Option<&'a str> {
+    input
+        .lines()
+        .find(|line| line.strip_prefix(name).is_some_and(|r| r.starts_with('{')))
+}
+
+// Rules of a workflow line, between its braces
+fn rules_span(line: &str) -> &str {
+    let start = line.find('{').map_or(0, |i| i + 1);
+    &line[start..line.len() - 1]
+}
+
+// Where rule `rule` (0-based) of `workflow` sends parts
+fn target_span<'a>(input: &'a str, workflow: &str, rule: usize) -> Option<&'a str> {
+    let rule = rules_span(workflow_line(input, workflow)?)
+        .split(',')
+        .nth(rule)?;
+    Some(rule.rsplit_once(':').map_or(rule, |(_, target)| target))
+}
+
+// Error pointing at the workflow or rule an issue comes from
+fn issue_error(input: &str, system: &System, issue: &Issue) -> ParseError {
+    let nowhere = &input[..0];
+    match issue {
+        Issue::MissingStart => ParseError::new(nowhere, "a workflow named in"),
+        Issue::UndefinedWorkflow { workflow, rule, .. } => ParseError::new(
+            target_span(input, workflow, *rule).unwrap_or(nowhere),
+            "a defined workflow",
+        ),
+        Issue::Cycle(cycle) => {
+            // The rule closing the loop, in the last workflow before it comes back
+            let (last, first) = (&cycle[cycle.len() - 2], &cycle[cycle.len() - 1]);
+            let rule = system.workflows[last]
+                .0
+                .iter()
+                .position(|r| r.result == RuleResult::Workflow(first.clone()))
+                .unwrap_or(0);
+            ParseError::new(
+                target_span(input, last, rule).unwrap_or(nowhere),
+                format!(
+                    "a workflow not looping back to {first} ({})",
+                    cycle.join(" -> ")
+                ),
+            )
+        }
+        Issue::FallThrough(workflow) => ParseError::after(
+            workflow_line(input, workflow).map_or(nowhere, rules_span),
+            "a last rule for the parts matching no other rule",
+        ),
+        Issue::ShadowedRule { workflow, .. } | Issue::Unreachable(workflow) => ParseError::new(
+            workflow_line(input, workflow).unwrap_or(nowhere),
+            issue.to_string(),
+        ),
+    }
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = System;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let system = parse(input)?;
+        if let Some(issue) = validate(&system).into_iter().find(Issue::is_error) {
+            return Err(issue_error(input, &system, &issue));
+        }
+        Ok(system)
+    }
+
+    fn part1(
+        System {
+            workflows, items, ..
+        }: &Self::Input,
+    ) -> Answer {
+        let mut count = 0;
+        for i in items {
+            match apply_workflows(i, workflows) {
+                WorkflowResult::Accepted => {
+                    count += i.total();
+                }
+                WorkflowResult::Rejected => {}
+            }
+        }
+        count.into()
+    }
+
+    fn part2(system: &Self::Input) -> Answer {
+        discover_combinations(system)
+            .iter()
+            .map(|r| r.combinations)
+            .sum::<usize>()
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(min: usize, max: usize) -> IntervalSet<usize> {
+        IntervalSet::from(min..max + 1)
+    }
+
+    fn xmas() -> Vec<String> {
+        ["x", "m", "a", "s"].map(String::from).to_vec()
+    }
+
+    fn ranges(sets: [IntervalSet<usize>; 4]) -> ItemRange {
+        ItemRange(xmas().into_iter().zip(sets).collect())
+    }
+
+    #[test]
+    fn test_item_range_greater() {
+        let range = ItemRange::new(&xmas())
+            .with_constraint("a", Condition::Greater, 2000)
+            .unwrap();
+        assert_eq!(
+            range,
+            ranges([set(1, 4000), set(1, 4000), set(2001, 4000), set(1, 4000)])
+        );
+
+        let range = range.with_constraint("a", Condition::Greater, 100).unwrap();
+
+        assert_eq!(
+            range,
+            ranges([set(1, 4000), set(1, 4000), set(2001, 4000), set(1, 4000)])
+        );
+
+        let range = range.with_constraint("x", Condition::Less, 100).unwrap();
+        assert_eq!(
+            range,
+            ranges([set(1, 99), set(1, 4000), set(2001, 4000), set(1, 4000)])
+        );
+
+        assert!(range.with_constraint("x", Condition::Greater, 99).is_none());
+
+        let range = range
+            .with_opposite_constraint("x", Condition::Greater, 100)
+            .unwrap();
+        assert_eq!(
+            range,
+            ranges([set(1, 99), set(1, 4000), set(2001, 4000), set(1, 4000)])
+        );
+
+        let range = range
+            .with_opposite_constraint("x", Condition::Greater, 99)
+            .unwrap();
+        assert_eq!(
+            range,
+            ranges([set(1, 99), set(1, 4000), set(2001, 4000), set(1, 4000)])
+        );
+
+        let range = range
+            .with_opposite_constraint("x", Condition::Greater, 98)
+            .unwrap();
+        assert_eq!(
+            range,
+            ranges([set(1, 98), set(1, 4000), set(2001, 4000), set(1, 4000)])
+        );
+
+        let range = range
+            .with_opposite_constraint("x", Condition::Less, 2)
+            .unwrap();
+        assert_eq!(
+            range,
+            ranges([set(2, 98), set(1, 4000), set(2001, 4000), set(1, 4000)])
+        );
+    }
+
+    #[test]
+    fn general_rules() {
+        let input = "in{x!=5:A,y>=3:R,ok}\nok{y<=1:A,y==2:A,R}\n\n{y=1,x=5}\n{x=5,y=4}\n{x=1,y=9}\n{y=2,x=5,z=7}\n";
+        let system = Day19::parse(input).unwrap();
+        assert_eq!(
+            system.attributes.iter().collect::<Vec<_>>(),
+            ["x", "y", "z"]
+        );
+        assert_eq!(Day19::part1(&system), Answer::from(6 + 10 + 14_usize));
+        // z is never tested, y can only be 1 or 2 when x is 5
+        assert_eq!(
+            Day19::part2(&system),
+            Answer::from((3999 * 4000 + 2) * 4000_usize)
+        );
+
+        let input = "in{x<3:A,R}\n\n{x=1,x=2}\n";
+        let err = Day19::parse(input).unwrap_err().locate(input);
+        assert_eq!(err.expected(), "a single rating for x");
+        let input = "in{x<3:A,R}\n\n{y=1}\n";
+        assert!(Day19::parse(input).is_err());
+        assert!(Day19::parse("in{x=3:A,R}\n").is_err());
+    }
+
+    #[test]
+    fn largest_threshold() {
+        let max = usize::MAX;
+        assert!(matching(Condition::Greater, max).ranges().is_empty());
+        assert!(matching(Condition::Equal, max).ranges().is_empty());
+        assert_eq!(
+            matching(Condition::LessOrEqual, max),
+            IntervalSet::from(0..max)
+        );
+        assert_eq!(
+            matching(Condition::NotEqual, max),
+            IntervalSet::from(0..max)
+        );
+
+        let input = format!("in{{x>{max}:R,A}}\n\n{{x=7}}\n");
+        let system = Day19::parse(&input).unwrap();
+        assert_eq!(Day19::part1(&system), Answer::from(7_usize));
+        assert_eq!(Day19::part2(&system), Answer::from(4000_usize));
+    }
+
+    #[test]
+    fn validates_workflows() {
+        let input = "in{x<10:a,b}\na{x>5:in,A,R}\nb{x<5:zz,x>100:R}\nc{A}\n\n{x=1}\n";
+        let system = parse(input).unwrap();
+        assert_eq!(
+            validate(&system),
+            [
+                Issue::ShadowedRule {
+                    workflow: "a".into(),
+                    rule: 2
+                },
+                Issue::UndefinedWorkflow {
+                    workflow: "b".into(),
+                    rule: 0,
+                    target: "zz".into()
+                },
+                Issue::Cycle(vec!["in".into(), "a".into(), "in".into()]),
+                Issue::FallThrough("b".into()),
+                Issue::Unreachable("c".into()),
+            ]
+        );
+
+        let err = Day19::parse(input).unwrap_err().locate(input);
+        assert_eq!(err.position(), Some((3, 7)));
+        assert_eq!(err.expected(), "a defined workflow");
+
+        let located = |input: &str| {
+            let err = Day19::parse(input).unwrap_err().locate(input);
+            (err.position().unwrap(), err.expected().to_owned())
+        };
+        assert_eq!(
+            located("in{x<10:a,R}\na{x>5:in,A}\n"),
+            (
+                (2, 7),
+                "a workflow not looping back to in (in -> a -> in)".into()
+            )
+        );
+        assert_eq!(
+            located("in{x<10:A}\n"),
+            (
+                (1, 10),
+                "a last rule for the parts matching no other rule".into()
+            )
+        );
+        assert_eq!(located("a{R}\n"), ((1, 1), "a workflow named in".into()));
+        // Ratings outside of the validated range could go through the workflows forever
+        assert_eq!(
+            located("in{x>0:A}\n\n{x=0}\n"),
+            ((3, 4), "a rating from 1 to 4000".into())
+        );
+
+        // Workflows looping back on each other for no part are fine
+        let input = "in{x<10:a,R}\na{x>20:in,A}\n";
+        assert_eq!(validate(&parse(input).unwrap()), []);
+        assert_eq!(validate(&parse("a{R}\n").unwrap()), [Issue::MissingStart]);
+        assert!(parse("in{A}\nin{R}\n").is_err());
+    }
+
+    #[test]
+    fn exports_regions() {
+        let input = "in{x<10:a,A}\na{y>=5:R,A}\n\n{x=1,y=1}\n";
+        let system = Day19::parse(input).unwrap();
+        let regions = discover_combinations(&system);
+        let paths: Vec<Vec<_>> = regions
+            .iter()
+            .map(|r| {
+                r.path()
+                    .iter()
+                    .map(|s| (s.workflow.as_str(), s.rule))
+                    .collect()
+            })
+            .collect();
+        assert_eq!(paths, [vec![("in", 0), ("a", 1)], vec![("in", 1)]]);
+        assert_eq!(regions[0].combinations(), 9 * 4);
+        assert_eq!(regions[0].ratings.to_string(), "x=1..=9 y=1..=4");
+
+        let json: serde_json::Value = serde_json::from_str(&to_json(&regions)).unwrap();
+        assert_eq!(json["combinations"], 9 * 4 + 3991 * 4000);
+        assert_eq!(
+            json["regions"][1]["ratings"]["x"],
+            serde_json::json!([[10, 4000]])
+        );
+        assert_eq!(json["regions"][0]["path"][0]["condition"], "x<10");
+
+        let dot = to_dot(&regions);
+        assert!(dot.contains("n0 -> n1 [label=\"#1 x<10\"];"));
+        assert!(dot.contains("n1 -> n2 [label=\"#2 otherwise\"];"));
+        assert_eq!(dot.matches(" -> ").count(), 3);
+    }
+}
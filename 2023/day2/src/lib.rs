@@ -0,0 +1,147 @@
+use common::parse;
+use common::{Answer, ParseError, Solution};
+
+#[derive(Debug)]
+struct Stage {
+    red: usize,
+    green: usize,
+    blue: usize,
+}
+
+impl Stage {
+    fn is_possible(&self, c: &Constraints) -> bool {
+        self.red <= c.red && self.green <= c.green && self.blue <= c.blue
+    }
+}
+
+#[derive(Debug)]
+pub struct Game {
+    game: usize,
+    stages: Vec<Stage>,
+}
+
+impl Game {
+    fn is_possible(&self, c: &Constraints) -> bool {
+        for stage in &self.stages {
+            if !stage.is_possible(c) {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn calculate_power(&self) -> usize {
+        let red = self.stages.iter().map(|s| s.red).max().unwrap();
+        let green = self.stages.iter().map(|s| s.green).max().unwrap();
+        let blue = self.stages.iter().map(|s| s.blue).max().unwrap();
+
+        red * green * blue
+    }
+}
+
+fn parse_stage(stage: &str) -> Result<Stage, ParseError> {
+    let color_iter = stage.split(',');
+    let mut red = 0;
+    let mut green = 0;
+    let mut blue = 0;
+
+    for colortxt in color_iter {
+        let colortxt = colortxt.trim();
+        if colortxt.is_empty() {
+            continue;
+        }
+
+        let mut split = colortxt.split_whitespace();
+        let value: usize = parse::number(parse::next(&mut split, colortxt, "a count")?)?;
+        let color_name = parse::next(&mut split, colortxt, "a color")?;
+        match color_name {
+            "blue" => {
+                blue += value;
+            }
+            "green" => {
+                green += value;
+            }
+            "red" => {
+                red += value;
+            }
+            c => {
+                return Err(ParseError::new(c, "one of red, green or blue"));
+            }
+        }
+    }
+
+    Ok(Stage { red, green, blue })
+}
+
+fn parse_line(line: &str) -> Result<Game, ParseError> {
+    let (game, stages_text) = parse::split_once(line, ":")?;
+    let game: usize = parse::number(parse::strip_prefix(game, "Game ")?.trim())?;
+    let mut stages = vec![];
+
+    for stage in stages_text.split(';') {
+        let stage = stage.trim();
+        if !stage.is_empty() {
+            stages.push(parse_stage(stage)?);
+        }
+    }
+    Ok(Game { game, stages })
+}
+
+struct Constraints {
+    red: usize,
+    green: usize,
+    blue: usize,
+}
+
+const CONSTRAINTS: Constraints = Constraints {
+    red: 12,
+    green: 13,
+    blue: 14,
+};
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<Game>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.lines().map(parse_line).collect()
+    }
+
+    fn part1(games: &Self::Input) -> Answer {
+        let mut count = 0;
+        for game in games {
+            if game.is_possible(&CONSTRAINTS) {
+                count += game.game;
+            }
+        }
+        count.into()
+    }
+
+    fn part2(games: &Self::Input) -> Answer {
+        let mut total_power = 0;
+        for game in games {
+            total_power += game.calculate_power();
+        }
+        total_power.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
+";
+
+    #[test]
+    fn solves_example() {
+        let input = Day2::parse(EXAMPLE).unwrap();
+        assert_eq!(Day2::part1(&input), Answer::from(8_usize));
+        assert_eq!(Day2::part2(&input), Answer::from(2286_usize));
+    }
+}
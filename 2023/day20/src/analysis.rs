@@ -0,0 +1,277 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{ModType, ModuleSpec, Pulse, Simulator};
+
+// Counters whose state doesn't repeat within this many presses are given up on
+const MAX_PRESSES: usize = 1 << 20;
+
+/// Part of the network fed by a single output of the broadcaster, which sends a high pulse to the
+/// conjunction in front of `rx` once per cycle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Counter {
+    /// Module the broadcaster sends pulses to
+    pub entry: String,
+    /// Module sending pulses to the conjunction in front of `rx`
+    pub exit: String,
+    /// Every module of the counter, sorted
+    pub modules: Vec<String>,
+    /// Presses before the modules enter a cycle
+    pub start: usize,
+    pub period: usize,
+    /// Press during which the exit sends its high pulse for the first time
+    pub high_at: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecomposeError {
+    NoBroadcaster,
+    /// `rx` has to be fed by a single conjunction, and nothing else
+    Sink(String),
+    /// The conjunction in front of `rx` also sends pulses to this module
+    CombinerOutput(String),
+    /// Both outputs of the broadcaster lead to modules connected to each other
+    Shared(String, String),
+    /// Modules fed by `entry` don't send pulses to the conjunction in front of `rx` through a
+    /// single module
+    Exits {
+        entry: String,
+        exits: Vec<String>,
+    },
+    /// This input of the conjunction in front of `rx` isn't fed by the broadcaster
+    Unpowered(String),
+    NoCycle {
+        entry: String,
+    },
+    /// The counter starting at `entry` sends `count` high pulses over its first cycle
+    HighPulses {
+        entry: String,
+        count: usize,
+    },
+    /// The counters never send their high pulse during the same press
+    NoAlignment,
+}
+
+impl std::fmt::Display for DecomposeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecomposeError::NoBroadcaster => write!(f, "there is no broadcaster"),
+            DecomposeError::Sink(reason) => {
+                write!(f, "rx must be fed by a single conjunction: {reason}")
+            }
+            DecomposeError::CombinerOutput(module) => {
+                write!(f, "the conjunction in front of rx also feeds {module}")
+            }
+            DecomposeError::Shared(a, b) => {
+                write!(f, "the counters starting at {a} and {b} are connected")
+            }
+            DecomposeError::Exits { entry, exits } => write!(
+                f,
+                "the counter starting at {entry} must reach rx through one module, found [{}]",
+                exits.join(", ")
+            ),
+            DecomposeError::Unpowered(module) => {
+                write!(f, "{module} feeds rx without being fed by the broadcaster")
+            }
+            DecomposeError::NoCycle { entry } => write!(
+                f,
+                "the counter starting at {entry} doesn't repeat within {MAX_PRESSES} presses"
+            ),
+            DecomposeError::HighPulses { entry, count } => write!(
+                f,
+                "the counter starting at {entry} sends {count} high pulses per cycle instead of one"
+            ),
+            DecomposeError::NoAlignment => {
+                write!(f, "the counters never send a high pulse on the same press")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DecomposeError {}
+
+/// Splits the network into the independent counters fed by the broadcaster and finds when each
+/// of them sends a high pulse to the conjunction in front of `rx`.
+pub fn counters(specs: &[ModuleSpec]) -> Result<Vec<Counter>, DecomposeError> {
+    let by_name: HashMap<&str, &ModuleSpec> = specs.iter().map(|s| (s.name.as_str(), s)).collect();
+    let inputs = |name: &str| -> Vec<&str> {
+        specs
+            .iter()
+            .filter(|s| s.connections.iter().any(|c| c == name))
+            .map(|s| s.name.as_str())
+            .collect()
+    };
+
+    let broadcaster = by_name
+        .get("broadcaster")
+        .ok_or(DecomposeError::NoBroadcaster)?;
+    let combiner = match inputs("rx")[..] {
+        [name] => name,
+        [] => {
+            return Err(DecomposeError::Sink(
+                "nothing sends pulses to rx".to_owned(),
+            ))
+        }
+        ref many => return Err(DecomposeError::Sink(format!("fed by {}", many.join(", ")))),
+    };
+    if (by_name[combiner].make)().ty() != ModType::Conjunction {
+        return Err(DecomposeError::Sink(format!(
+            "{combiner} isn't a conjunction"
+        )));
+    }
+    if let Some(other) = by_name[combiner].connections.iter().find(|c| *c != "rx") {
+        return Err(DecomposeError::CombinerOutput(other.clone()));
+    }
+
+    // Modules connected to each other in either direction, leaving out the ones every counter
+    // goes through
+    let shared = ["broadcaster", "button", combiner, "rx"];
+    let mut neighbours: HashMap<&str, Vec<&str>> = HashMap::new();
+    for spec in specs {
+        for dest in &spec.connections {
+            if !shared.contains(&spec.name.as_str()) && !shared.contains(&dest.as_str()) {
+                neighbours.entry(&spec.name).or_default().push(dest);
+                neighbours.entry(dest).or_default().push(&spec.name);
+            }
+        }
+    }
+
+    let mut counters = vec![];
+    for entry in &broadcaster.connections {
+        // Every module connected to the entry, which mustn't lead to another entry
+        let mut modules = vec![entry.as_str()];
+        let mut seen = HashSet::from([entry.as_str()]);
+        let mut idx = 0;
+        while idx < modules.len() {
+            for n in neighbours.get(modules[idx]).into_iter().flatten() {
+                if broadcaster.connections.iter().any(|e| e == n) && n != entry {
+                    return Err(DecomposeError::Shared(entry.clone(), n.to_string()));
+                }
+                if seen.insert(n) {
+                    modules.push(n);
+                }
+            }
+            idx += 1;
+        }
+        modules.sort();
+
+        let exits: Vec<_> = modules
+            .iter()
+            .filter(|m| {
+                by_name
+                    .get(*m)
+                    .is_some_and(|s| s.connections.iter().any(|c| c == combiner))
+            })
+            .map(|m| m.to_string())
+            .collect();
+        let [exit] = &exits[..] else {
+            return Err(DecomposeError::Exits {
+                entry: entry.clone(),
+                exits,
+            });
+        };
+        counters.push(run_counter(specs, entry, exit, combiner, &modules)?);
+    }
+
+    if let Some(unpowered) = inputs(combiner)
+        .into_iter()
+        .find(|i| !counters.iter().any(|c| c.exit == *i))
+    {
+        return Err(DecomposeError::Unpowered(unpowered.to_owned()));
+    }
+    Ok(counters)
+}
+
+// Simulates a counter on its own until its modules come back to a state they were already in
+fn run_counter(
+    specs: &[ModuleSpec],
+    entry: &str,
+    exit: &str,
+    combiner: &str,
+    modules: &[&str],
+) -> Result<Counter, DecomposeError> {
+    let mut sub_specs: Vec<_> = specs
+        .iter()
+        .filter(|s| modules.contains(&s.name.as_str()))
+        .cloned()
+        .collect();
+    let broadcaster = specs.iter().find(|s| s.name == "broadcaster").unwrap();
+    sub_specs.push(ModuleSpec {
+        connections: vec![entry.to_owned()],
+        ..broadcaster.clone()
+    });
+
+    let mut simulator = Simulator::new(&sub_specs);
+    let mut seen = HashMap::from([(simulator.snapshot().module_states().to_vec(), 0)]);
+    let mut highs = HashSet::new();
+    let (start, period) = loop {
+        simulator.press_with(1, |e| {
+            if e.source == exit && e.dest == combiner && e.pulse == Pulse::High {
+                highs.insert(e.press);
+            }
+        });
+        let presses = simulator.presses();
+        let state = simulator.snapshot().module_states().to_vec();
+        if let Some(prev) = seen.insert(state, presses) {
+            break (prev, presses - prev);
+        }
+        if presses == MAX_PRESSES {
+            return Err(DecomposeError::NoCycle {
+                entry: entry.to_owned(),
+            });
+        }
+    };
+
+    // A high pulse before the cycle would only happen once, so it has to be on the cycle
+    let high_at = match highs.iter().collect::<Vec<_>>()[..] {
+        [h] if *h > start => *h,
+        _ => {
+            return Err(DecomposeError::HighPulses {
+                entry: entry.to_owned(),
+                count: highs.len(),
+            })
+        }
+    };
+
+    Ok(Counter {
+        entry: entry.to_owned(),
+        exit: exit.to_owned(),
+        modules: modules.iter().map(|m| m.to_string()).collect(),
+        start,
+        period,
+        high_at,
+    })
+}
+
+// Greatest common divisor `g` of `a` and `b`, along with `x` and `y` such that `a*x + b*y = g`
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (g, x, y) = extended_gcd(b, a % b);
+        (g, y, x - a / b * y)
+    }
+}
+
+/// First press during which every counter sends its high pulse. The conjunction in front of `rx`
+/// is assumed to see all of them before any counter resets its exit to low.
+pub fn first_alignment(counters: &[Counter]) -> Result<usize, DecomposeError> {
+    // Presses `p` such that `p = value (mod modulus)`, combined with the chinese remainder theorem
+    let (mut value, mut modulus) = (0i128, 1i128);
+    for c in counters {
+        let (target, period) = (c.high_at as i128, c.period as i128);
+        let (g, x, _) = extended_gcd(modulus, period);
+        if (target - value) % g != 0 {
+            return Err(DecomposeError::NoAlignment);
+        }
+        let lcm = modulus / g * period;
+        value = (value + (target - value) / g * x % (period / g) * modulus).rem_euclid(lcm);
+        modulus = lcm;
+    }
+
+    // Counters only start sending high pulses after a while, and pressing 0 times doesn't count
+    let first = counters.iter().map(|c| c.high_at).max().unwrap_or(1) as i128;
+    if value < first {
+        value += (first - value + modulus - 1) / modulus * modulus;
+    }
+    Ok(value as usize)
+}
@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+
+use crate::{Event, ModType, ModuleRouting, Pulse};
+
+/// Low and high pulses sent along each connection, filled from the events of a simulation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PulseCounts(HashMap<(String, String), (usize, usize)>);
+
+impl PulseCounts {
+    pub fn record(&mut self, event: &Event) {
+        let counts = self
+            .0
+            .entry((event.source.to_owned(), event.dest.to_owned()))
+            .or_default();
+        match event.pulse {
+            Pulse::Low => counts.0 += 1,
+            Pulse::High => counts.1 += 1,
+        }
+    }
+
+    /// Low and high pulses sent from `source` to `dest`.
+    pub fn get(&self, source: &str, dest: &str) -> (usize, usize) {
+        self.0
+            .get(&(source.to_owned(), dest.to_owned()))
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+// Modules and the modules they send pulses to, sorted by name. Destinations that aren't defined
+// are sinks and have no type.
+fn nodes(routing: &ModuleRouting) -> Vec<(&str, Option<ModType>, &[String])> {
+    let mut nodes: Vec<_> = routing
+        .modules
+        .iter()
+        .map(|(name, (module, outs))| (name.as_str(), Some(module.ty()), &outs[..]))
+        .collect();
+    for (_, outs) in routing.modules.values() {
+        for out in outs {
+            if !routing.modules.contains_key(out) && !nodes.iter().any(|n| n.0 == out) {
+                nodes.push((out, None, &[]));
+            }
+        }
+    }
+    nodes.sort_by_key(|n| n.0);
+    nodes
+}
+
+fn edge_label(counts: Option<&PulseCounts>, source: &str, dest: &str) -> Option<String> {
+    let (low, high) = counts?.get(source, dest);
+    Some(format!("{low} low, {high} high"))
+}
+
+impl ModuleRouting {
+    /// Graphviz graph of the modules, shaped by type, with the `rx` sink highlighted. Edges are
+    /// labelled with the pulses they carried when `counts` are given.
+    pub fn to_dot(&self, counts: Option<&PulseCounts>) -> String {
+        let mut out = String::from("digraph modules {\n");
+        for (name, ty, _) in nodes(self) {
+            let attrs = match ty {
+                Some(ModType::FlipFlop) => format!("label=\"%{name}\", shape=ellipse"),
+                Some(ModType::Conjunction) => format!("label=\"&{name}\", shape=box"),
+                Some(ModType::Broadcast) => format!("label=\"{name}\", shape=doubleoctagon"),
+                Some(ModType::Other(kind)) => {
+                    format!("label=\"{kind}\\n{name}\", shape=parallelogram")
+                }
+                None if name == "rx" => {
+                    format!("label=\"{name}\", shape=doublecircle, color=red, penwidth=2")
+                }
+                None => format!("label=\"{name}\", shape=plaintext"),
+            };
+            out += &format!("    \"{name}\" [{attrs}];\n");
+        }
+        for (name, _, outs) in nodes(self) {
+            for dest in outs {
+                match edge_label(counts, name, dest) {
+                    Some(label) => {
+                        out += &format!("    \"{name}\" -> \"{dest}\" [label=\"{label}\"];\n")
+                    }
+                    None => out += &format!("    \"{name}\" -> \"{dest}\";\n"),
+                }
+            }
+        }
+        out += "}\n";
+        out
+    }
+
+    /// Same graph as [`ModuleRouting::to_dot`], as a Mermaid flowchart.
+    pub fn to_mermaid(&self, counts: Option<&PulseCounts>) -> String {
+        let mut out = String::from("flowchart LR\n");
+        for (name, ty, _) in nodes(self) {
+            let node = match ty {
+                Some(ModType::FlipFlop) => format!("{name}([\"%{name}\"])"),
+                Some(ModType::Conjunction) => format!("{name}{{{{\"&{name}\"}}}}"),
+                Some(ModType::Broadcast) => format!("{name}[[\"{name}\"]]"),
+                Some(ModType::Other(kind)) => format!("{name}[/\"{kind} {name}\"/]"),
+                None if name == "rx" => format!("{name}(((\"{name}\"))):::rx"),
+                None => format!("{name}>\"{name}\"]"),
+            };
+            out += &format!("    {node}\n");
+        }
+        for (name, _, outs) in nodes(self) {
+            for dest in outs {
+                match edge_label(counts, name, dest) {
+                    Some(label) => out += &format!("    {name} -->|\"{label}\"| {dest}\n"),
+                    None => out += &format!("    {name} --> {dest}\n"),
+                }
+            }
+        }
+        out += "    classDef rx stroke:#d00,stroke-width:3px\n";
+        out
+    }
+}
@@ -0,0 +1,510 @@
+use std::collections::{BTreeMap, HashMap};
+
+use common::parse;
+use common::{Answer, ParseError, Solution};
+use serde::{Deserialize, Serialize};
+
+mod analysis;
+mod export;
+mod simulator;
+mod trace;
+
+pub use analysis::{counters, first_alignment, Counter, DecomposeError};
+pub use export::PulseCounts;
+pub use simulator::{Event, Simulator, Snapshot};
+pub use trace::{read_trace, replay, Mismatch, TraceEvent, TraceFormat, TraceWriter};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Pulse {
+    Low,
+    High,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModType {
+    FlipFlop,
+    Conjunction,
+    Broadcast,
+    /// Module kind registered outside of this crate
+    Other(&'static str),
+}
+
+pub trait Module {
+    fn update_input(&mut self, source: &str);
+    fn execute(&mut self, source: &str, pulse: Pulse) -> Option<Pulse>;
+    fn reset(&mut self);
+    fn ty(&self) -> ModType;
+    /// Internal state, enough for [`Module::restore`] to bring the module back to it.
+    fn state(&self) -> Vec<u64>;
+    fn restore(&mut self, state: &[u64]);
+}
+
+#[derive(Debug)]
+struct FlipFlop {
+    state: bool,
+}
+
+impl FlipFlop {
+    fn new() -> Self {
+        Self { state: false }
+    }
+}
+
+impl Module for FlipFlop {
+    fn update_input(&mut self, _source: &str) {}
+
+    fn execute(&mut self, _source: &str, pulse: Pulse) -> Option<Pulse> {
+        match pulse {
+            Pulse::Low => {
+                self.state = !self.state;
+                Some(if self.state { Pulse::High } else { Pulse::Low })
+            }
+            Pulse::High => None,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.state = false;
+    }
+
+    fn ty(&self) -> ModType {
+        ModType::FlipFlop
+    }
+
+    fn state(&self) -> Vec<u64> {
+        vec![self.state as u64]
+    }
+
+    fn restore(&mut self, state: &[u64]) {
+        self.state = state[0] != 0;
+    }
+}
+
+#[derive(Debug)]
+struct Conjunction {
+    // Ordered so that the state lists the inputs in a stable order
+    sources: BTreeMap<String, Pulse>,
+}
+
+impl Conjunction {
+    fn new() -> Self {
+        Self {
+            sources: BTreeMap::new(),
+        }
+    }
+}
+
+impl Module for Conjunction {
+    fn update_input(&mut self, source: &str) {
+        self.sources.insert(source.to_string(), Pulse::Low);
+    }
+
+    fn execute(&mut self, source: &str, pulse: Pulse) -> Option<Pulse> {
+        let state = self
+            .sources
+            .get_mut(source)
+            .expect("Input to the conjunction exists!");
+        *state = pulse;
+        if self.sources.iter().all(|(_s, p)| *p == Pulse::High) {
+            Some(Pulse::Low)
+        } else {
+            Some(Pulse::High)
+        }
+    }
+
+    fn reset(&mut self) {
+        for (_, pulse) in self.sources.iter_mut() {
+            *pulse = Pulse::Low
+        }
+    }
+
+    fn ty(&self) -> ModType {
+        ModType::Conjunction
+    }
+
+    fn state(&self) -> Vec<u64> {
+        self.sources
+            .values()
+            .map(|p| (*p == Pulse::High) as u64)
+            .collect()
+    }
+
+    fn restore(&mut self, state: &[u64]) {
+        for (pulse, s) in self.sources.values_mut().zip(state) {
+            *pulse = if *s != 0 { Pulse::High } else { Pulse::Low };
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Broadcast {}
+
+impl Broadcast {
+    fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Module for Broadcast {
+    fn update_input(&mut self, _source: &str) {}
+
+    fn execute(&mut self, _source: &str, pulse: Pulse) -> Option<Pulse> {
+        Some(pulse)
+    }
+
+    fn reset(&mut self) {}
+
+    fn ty(&self) -> ModType {
+        ModType::Broadcast
+    }
+
+    fn state(&self) -> Vec<u64> {
+        vec![]
+    }
+
+    fn restore(&mut self, _state: &[u64]) {}
+}
+
+#[derive(Default)]
+pub struct ModuleRouting {
+    // map from module name to actual module and names of connected modules
+    modules: HashMap<String, (Box<dyn Module>, Vec<String>)>,
+}
+
+impl ModuleRouting {
+    pub fn new() -> Self {
+        Self {
+            modules: HashMap::new(),
+        }
+    }
+
+    pub fn insert(&mut self, name: String, module: Box<dyn Module>, connections: Vec<String>) {
+        self.modules.insert(name, (module, connections));
+    }
+
+    pub fn update_inputs(&mut self) {
+        let keys: Vec<String> = self.modules.keys().cloned().collect();
+        for source in keys {
+            let destinations = self.modules[&source].1.clone();
+            for destination in destinations {
+                if let Some((module, _outs)) = self.modules.get_mut(&destination) {
+                    module.update_input(&source);
+                }
+            }
+        }
+    }
+
+    pub fn reset(&mut self) {
+        for (_, (module, _)) in self.modules.iter_mut() {
+            module.reset();
+        }
+    }
+}
+
+impl std::fmt::Debug for ModuleRouting {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Modules {{")?;
+        for module in &self.modules {
+            writeln!(f, "\t{} => {:?}", module.0, module.1 .1)?;
+        }
+        writeln!(f, "}}")
+    }
+}
+
+/// Builds a fresh module of some kind.
+pub type MakeModule = fn() -> Box<dyn Module>;
+
+#[derive(Clone)]
+pub struct ModuleSpec {
+    name: String,
+    make: MakeModule,
+    connections: Vec<String>,
+}
+
+/// Module kinds known to the parser, by the prefix of their name. The broadcaster is always
+/// known, by its name.
+#[derive(Clone)]
+pub struct ModuleKinds(Vec<(&'static str, MakeModule)>);
+
+impl Default for ModuleKinds {
+    fn default() -> Self {
+        Self(vec![
+            ("%", || Box::new(FlipFlop::new())),
+            ("&", || Box::new(Conjunction::new())),
+        ])
+    }
+}
+
+impl ModuleKinds {
+    /// Modules whose name starts with `prefix` are built with `make`.
+    pub fn register(&mut self, prefix: &'static str, make: MakeModule) {
+        self.0.push((prefix, make));
+    }
+}
+
+fn build_routing(specs: &[ModuleSpec]) -> ModuleRouting {
+    let mut modules = ModuleRouting::new();
+    for spec in specs {
+        modules.insert(spec.name.clone(), (spec.make)(), spec.connections.clone());
+    }
+    modules.update_inputs();
+    modules
+}
+
+fn parse_module(line: &str, kinds: &ModuleKinds) -> Result<ModuleSpec, ParseError> {
+    let (module, connections) = parse::split_once(line, "->")?;
+    let module = module.trim();
+    let kind = kinds
+        .0
+        .iter()
+        .find_map(|(prefix, make)| Some((module.strip_prefix(prefix)?, *make)));
+    let (name, make) = match kind {
+        Some(kind) => kind,
+        None if module == "broadcaster" => (module, (|| Box::new(Broadcast::new())) as MakeModule),
+        None => {
+            let prefixes: Vec<_> = kinds.0.iter().map(|(prefix, _)| *prefix).collect();
+            return Err(ParseError::new(
+                module,
+                format!(
+                    "the broadcaster or a module name prefixed by one of {}",
+                    prefixes.join(" ")
+                ),
+            ));
+        }
+    };
+    if name.is_empty() {
+        return Err(ParseError::after(module, "a module name"));
+    }
+
+    let connections = connections
+        .split(',')
+        .map(|l| match l.trim() {
+            "" => Err(ParseError::new(l, "a module name")),
+            l => Ok(l.to_string()),
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(ModuleSpec {
+        name: name.to_string(),
+        make,
+        connections,
+    })
+}
+
+/// Parses a module configuration with extra module kinds.
+pub fn parse(input: &str, kinds: &ModuleKinds) -> Result<Vec<ModuleSpec>, ParseError> {
+    input
+        .lines()
+        .map(|line| parse_module(line, kinds))
+        .collect()
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input = Vec<ModuleSpec>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input, &ModuleKinds::default())
+    }
+
+    fn part1(specs: &Self::Input) -> Answer {
+        let mut simulator = Simulator::new(specs);
+        simulator.press(1000);
+        let (low, high) = simulator.counts();
+        (low * high).into()
+    }
+
+    fn part2(specs: &Self::Input) -> Answer {
+        // rx gets a low pulse once every counter feeding it sends a high pulse on the same press
+        match counters(specs).and_then(|counters| first_alignment(&counters)) {
+            Ok(presses) => presses.into(),
+            Err(e) => format!("cannot find when rx gets a low pulse: {e}").into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str =
+        "broadcaster -> a\n%a -> inv, con\n&inv -> b\n%b -> con\n&con -> output\n";
+
+    struct Inverter;
+
+    impl Module for Inverter {
+        fn update_input(&mut self, _source: &str) {}
+
+        fn execute(&mut self, _source: &str, pulse: Pulse) -> Option<Pulse> {
+            match pulse {
+                Pulse::Low => Some(Pulse::High),
+                Pulse::High => Some(Pulse::Low),
+            }
+        }
+
+        fn reset(&mut self) {}
+
+        fn ty(&self) -> ModType {
+            ModType::Other("inverter")
+        }
+
+        fn state(&self) -> Vec<u64> {
+            vec![]
+        }
+
+        fn restore(&mut self, _state: &[u64]) {}
+    }
+
+    #[test]
+    fn custom_modules() {
+        let input = "broadcaster -> n\n!n -> a\n%a -> out\n";
+        assert!(Day20::parse(input).is_err());
+
+        let mut kinds = ModuleKinds::default();
+        kinds.register("!", || Box::new(Inverter));
+        let specs = parse(input, &kinds).unwrap();
+
+        let mut events = vec![];
+        let mut simulator = Simulator::new(&specs);
+        simulator.observe(|e| events.push((e.seq, e.dest.to_owned(), e.pulse)));
+        simulator.press(1);
+        assert_eq!(simulator.counts(), (2, 1));
+        drop(simulator);
+
+        assert_eq!(
+            events,
+            [
+                (0, "broadcaster".to_owned(), Pulse::Low),
+                (1, "n".to_owned(), Pulse::Low),
+                (2, "a".to_owned(), Pulse::High),
+            ]
+        );
+    }
+
+    #[test]
+    fn snapshots() {
+        let specs = Day20::parse(EXAMPLE).unwrap();
+        let mut simulator = Simulator::new(&specs);
+        simulator.press(1);
+        let snapshot = simulator.snapshot();
+
+        simulator.press(3);
+        let after = simulator.snapshot();
+        assert_ne!(after, snapshot);
+
+        simulator.restore(&snapshot);
+        assert_eq!(simulator.snapshot(), snapshot);
+        assert_eq!(simulator.presses(), 1);
+        simulator.press(3);
+        assert_eq!(simulator.snapshot(), after);
+
+        simulator.press(996);
+        assert_eq!(simulator.counts(), (4250, 2750));
+    }
+
+    #[test]
+    fn decomposes_counters() {
+        let input = "broadcaster -> a0, b0\n%a0 -> a1, ha\n%a1 -> a2\n%a2 -> ha\n&ha -> a1, a0, ia\n\
+                     &ia -> fin\n%b0 -> b1, hb\n%b1 -> b2, hb\n%b2 -> hb\n&hb -> b0, ib\n&ib -> fin\n\
+                     &fin -> rx\n";
+        let specs = Day20::parse(input).unwrap();
+        let found = counters(&specs).unwrap();
+        let periods: Vec<_> = found.iter().map(|c| (c.exit.as_str(), c.period)).collect();
+        assert_eq!(periods, [("ia", 5), ("ib", 7)]);
+        assert_eq!(first_alignment(&found), Ok(35));
+
+        let mut simulator = Simulator::new(&specs);
+        let mut low_to_rx = false;
+        while !low_to_rx {
+            simulator.press_with(1, |e| low_to_rx |= e.dest == "rx" && e.pulse == Pulse::Low);
+        }
+        assert_eq!(simulator.presses(), 35);
+
+        let shared = input.replace("%a2 -> ha", "%a2 -> ha, b1");
+        assert_eq!(
+            counters(&Day20::parse(&shared).unwrap()),
+            Err(DecomposeError::Shared("a0".into(), "b0".into()))
+        );
+        assert!(matches!(
+            counters(&Day20::parse(EXAMPLE).unwrap()),
+            Err(DecomposeError::Sink(_))
+        ));
+        assert!(matches!(
+            Day20::part2(&Day20::parse(EXAMPLE).unwrap()),
+            Answer::Text(t) if t.starts_with("cannot find when rx gets a low pulse")
+        ));
+    }
+
+    #[test]
+    fn aligns_offset_counters() {
+        let counter = |high_at, period| Counter {
+            entry: String::new(),
+            exit: String::new(),
+            modules: vec![],
+            start: 0,
+            period,
+            high_at,
+        };
+        assert_eq!(first_alignment(&[counter(3, 5), counter(4, 7)]), Ok(18));
+        assert_eq!(first_alignment(&[counter(10, 4), counter(6, 6)]), Ok(18));
+        assert_eq!(
+            first_alignment(&[counter(2, 4), counter(1, 6)]),
+            Err(DecomposeError::NoAlignment)
+        );
+    }
+
+    #[test]
+    fn exports_graph() {
+        let specs = Day20::parse(&EXAMPLE.replace("output", "rx")).unwrap();
+        let mut counts = PulseCounts::default();
+        let mut simulator = Simulator::new(&specs);
+        simulator.press_with(2, |e| counts.record(e));
+        assert_eq!(counts.get("a", "con"), (1, 1));
+
+        let dot = simulator.routing().to_dot(Some(&counts));
+        assert!(dot.contains("\"a\" [label=\"%a\", shape=ellipse];"));
+        assert!(dot.contains("\"rx\" [label=\"rx\", shape=doublecircle, color=red, penwidth=2];"));
+        assert!(dot.contains("\"a\" -> \"con\" [label=\"1 low, 1 high\"];"));
+        assert_eq!(dot.matches(" -> ").count(), 6);
+
+        let mermaid = simulator.routing().to_mermaid(None);
+        assert!(mermaid.contains("    inv{{\"&inv\"}}\n"));
+        assert!(mermaid.contains("    rx(((\"rx\"))):::rx\n"));
+        assert!(mermaid.contains("    broadcaster --> a\n"));
+    }
+
+    #[test]
+    fn traces() {
+        let specs = Day20::parse(EXAMPLE).unwrap();
+        for format in [TraceFormat::Jsonl, TraceFormat::Binary] {
+            let mut writer = TraceWriter::new(vec![], format);
+            Simulator::new(&specs).press_with(4, |e| writer.record(e));
+            let bytes = writer.finish().unwrap();
+
+            let trace = read_trace(&bytes[..]).unwrap();
+            // The example repeats every 4 presses, sending 17 low and 11 high pulses
+            assert_eq!(trace.len(), 28);
+            assert_eq!(trace[1].to_string(), "press 1 #1: broadcaster -low-> a");
+            assert_eq!(replay(&specs, &trace), Ok(()));
+
+            let mut changed = trace.clone();
+            changed[5].pulse = match changed[5].pulse {
+                Pulse::Low => Pulse::High,
+                Pulse::High => Pulse::Low,
+            };
+            assert_eq!(replay(&specs, &changed).unwrap_err().index, 5);
+            assert_eq!(
+                replay(&specs, &trace[..trace.len() - 1])
+                    .unwrap_err()
+                    .expected,
+                None
+            );
+        }
+
+        let line = "{\"press\":1,\"seq\":0,\"source\":\"button\",\"dest\":\"broadcaster\",\"pulse\":\"low\"}\n";
+        assert_eq!(read_trace(line.as_bytes()).unwrap().len(), 1);
+        assert!(read_trace(&b"PULS1\x01\x01\x00\x05\x00"[..]).is_err());
+    }
+}
@@ -1,317 +1,125 @@
-use std::collections::HashMap;
-use std::collections::VecDeque;
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Pulse {
-    Low,
-    High,
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum ModType {
-    FlipFlop,
-    Conjunction,
-    Broadcast,
-}
-
-trait Module {
-    fn update_input(&mut self, source: &str);
-    fn execute(&mut self, source: &str, pulse: Pulse) -> Option<Pulse>;
-    fn reset(&mut self);
-    fn ty(&self) -> ModType;
-}
-
-#[derive(Debug)]
-struct FlipFlop {
-    state: bool,
-}
-
-impl FlipFlop {
-    fn new() -> Self {
-        Self { state: false }
-    }
-}
-
-impl Module for FlipFlop {
-    fn update_input(&mut self, _source: &str) {}
-
-    fn execute(&mut self, _source: &str, pulse: Pulse) -> Option<Pulse> {
-        match pulse {
-            Pulse::Low => {
-                self.state = !self.state;
-                Some(if self.state { Pulse::High } else { Pulse::Low })
+use std::fs::File;
+use std::io::{BufWriter, Read};
+use std::path::PathBuf;
+
+use anyhow::{anyhow, bail, Context};
+use common::Solution;
+use day20::{PulseCounts, Simulator, TraceFormat, TraceWriter};
+
+const USAGE: &str =
+    "usage: day20 [--counters | --dot | --mermaid | --trace <file> | --replay <file>]
+             [--presses <n>] [--format jsonl|binary] < input";
+
+// Presses recorded by --trace when not given
+const TRACE_PRESSES: usize = 1000;
+
+#[derive(PartialEq, Eq)]
+enum Mode {
+    Solve,
+    Counters,
+    Dot,
+    Mermaid,
+    Trace(PathBuf),
+    Replay(PathBuf),
+}
+
+fn main() -> anyhow::Result<()> {
+    let mut mode = Mode::Solve;
+    let mut presses = None;
+    let mut format = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(anyhow!("Missing value for {arg}"));
+        let selected = match arg.as_str() {
+            "--counters" => Mode::Counters,
+            "--dot" => Mode::Dot,
+            "--mermaid" => Mode::Mermaid,
+            "--trace" => Mode::Trace(value()?.into()),
+            "--replay" => Mode::Replay(value()?.into()),
+            "--presses" => {
+                presses = Some(
+                    value()?
+                        .parse::<usize>()
+                        .with_context(|| format!("Invalid value for {arg}"))?,
+                );
+                continue;
             }
-            Pulse::High => None,
-        }
-    }
-
-    fn reset(&mut self) {
-        self.state = false;
-    }
-
-    fn ty(&self) -> ModType {
-        ModType::FlipFlop
-    }
-}
-
-#[derive(Debug)]
-struct Conjunction {
-    sources: HashMap<String, Pulse>,
-}
-
-impl Conjunction {
-    fn new() -> Self {
-        Self {
-            sources: HashMap::new(),
-        }
-    }
-}
-
-impl Module for Conjunction {
-    fn update_input(&mut self, source: &str) {
-        self.sources.insert(source.to_string(), Pulse::Low);
-    }
-
-    fn execute(&mut self, source: &str, pulse: Pulse) -> Option<Pulse> {
-        let state = self
-            .sources
-            .get_mut(source)
-            .expect("Input to the conjunction exists!");
-        *state = pulse;
-        if self.sources.iter().all(|(_s, p)| *p == Pulse::High) {
-            Some(Pulse::Low)
-        } else {
-            Some(Pulse::High)
-        }
-    }
-
-    fn reset(&mut self) {
-        for (_, pulse) in self.sources.iter_mut() {
-            *pulse = Pulse::Low
-        }
-    }
-
-    fn ty(&self) -> ModType {
-        ModType::Conjunction
-    }
-}
-
-#[derive(Debug)]
-struct Broadcast {}
-
-impl Broadcast {
-    fn new() -> Self {
-        Self {}
-    }
-}
-
-impl Module for Broadcast {
-    fn update_input(&mut self, _source: &str) {}
-
-    fn execute(&mut self, _source: &str, pulse: Pulse) -> Option<Pulse> {
-        Some(pulse)
-    }
-
-    fn reset(&mut self) {}
-
-    fn ty(&self) -> ModType {
-        ModType::Broadcast
-    }
-}
-
-struct ModuleRouting {
-    // map from module name to actual module and names of connected modules
-    modules: HashMap<String, (Box<dyn Module>, Vec<String>)>,
-}
-
-impl ModuleRouting {
-    fn new() -> Self {
-        Self {
-            modules: HashMap::new(),
-        }
-    }
-
-    fn insert(&mut self, name: String, module: Box<dyn Module>, connections: Vec<String>) {
-        self.modules.insert(name, (module, connections));
-    }
-
-    fn update_inputs(&mut self) {
-        let keys: Vec<String> = self.modules.keys().cloned().collect();
-        for source in keys {
-            let destinations = self.modules[&source].1.clone();
-            for destination in destinations {
-                if let Some((module, _outs)) = self.modules.get_mut(&destination) {
-                    module.update_input(&source);
-                }
+            "--format" => {
+                format = Some(match value()?.as_str() {
+                    "jsonl" => TraceFormat::Jsonl,
+                    "binary" => TraceFormat::Binary,
+                    f => bail!("Unknown trace format {f:?}, expected jsonl or binary"),
+                });
+                continue;
             }
+            a => bail!("Unexpected argument {a:?}\n{USAGE}"),
+        };
+        if mode != Mode::Solve {
+            bail!("Only one of --counters, --dot, --mermaid, --trace and --replay can be given\n{USAGE}");
         }
+        mode = selected;
     }
-
-    fn reset(&mut self) {
-        for (_, (module, _)) in self.modules.iter_mut() {
-            module.reset();
-        }
-    }
-}
-
-impl std::fmt::Debug for ModuleRouting {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Modules {{\n")?;
-        for module in &self.modules {
-            write!(f, "\t{} => {:?}\n", module.0, module.1 .1)?;
-        }
-        write!(f, "}}\n")
-    }
-}
-
-fn gcd(mut a: usize, mut b: usize) -> usize {
-    if a == b {
-        return a;
+    if presses.is_some() && !matches!(mode, Mode::Dot | Mode::Mermaid | Mode::Trace(_)) {
+        bail!("--presses only applies to --dot, --mermaid and --trace\n{USAGE}");
     }
-
-    if b > a {
-        std::mem::swap(&mut a, &mut b);
+    if format.is_some() && !matches!(mode, Mode::Trace(_)) {
+        bail!("--format only applies to --trace\n{USAGE}");
     }
 
-    while b != 0 {
-        let temp = a;
-        a = b;
-        b = temp % b;
+    if mode == Mode::Solve {
+        return common::run_stdin::<day20::Day20>();
     }
 
-    a
-}
-
-fn lcm(a: usize, b: usize) -> usize {
-    a * b / gcd(a, b)
-}
-
-fn main() {
-    let lines: Vec<String> = std::io::stdin().lines().map(|l| l.unwrap()).collect();
+    let mut text = String::new();
+    std::io::stdin().lock().read_to_string(&mut text)?;
+    let specs = day20::Day20::parse(&text).map_err(|e| e.locate(&text))?;
 
-    let mut modules = ModuleRouting::new();
-    for line in &lines {
-        let mut iter = line.split("->");
-        let module = iter.next().unwrap().trim();
-        let (name, module): (String, Box<dyn Module>) = if module.starts_with("%") {
-            (
-                module.trim_start_matches("%").to_string(),
-                Box::new(FlipFlop::new()),
-            )
-        } else if module.starts_with("&") {
-            (
-                module.trim_start_matches("&").to_string(),
-                Box::new(Conjunction::new()),
-            )
-        } else if module == "broadcaster" {
-            (module.to_string(), Box::new(Broadcast::new()))
-        } else {
-            unimplemented!();
-        };
-        let connections: Vec<String> = iter
-            .next()
-            .unwrap()
-            .trim()
-            .split(",")
-            .map(|l| l.trim().to_string())
-            .collect();
-
-        modules.insert(name, module, connections);
-    }
-    modules.update_inputs();
-    println!("{modules:?}");
-
-    // Single button press
-
-    let mut deque = VecDeque::new();
-    let mut high = 0usize;
-    let mut low = 0usize;
-
-    for _i in 0..1000 {
-        deque.push_back((Pulse::Low, "button".to_owned(), "broadcaster".to_owned()));
-
-        while let Some((pulse, source, dest)) = deque.pop_front() {
-            match pulse {
-                Pulse::Low => low += 1,
-                Pulse::High => high += 1,
-            };
-
-            if let Some((module, outputs)) = modules.modules.get_mut(&dest) {
-                if let Some(out_pulse) = module.execute(&source, pulse) {
-                    for out in &*outputs {
-                        deque.push_back((out_pulse, dest.clone(), out.to_string()));
-                    }
-                }
-            }
-        }
-    }
-    println!("low = {low}, high = {high}, result = {}", low * high);
-    modules.reset();
-
-    let sources_to_rx: Vec<String> = modules
-        .modules
-        .iter()
-        .filter_map(|(name, (module, outs))| {
-            if outs.iter().find(|n| *n == "rx").is_none() {
-                return None;
-            }
-            assert_eq!(module.ty(), ModType::Conjunction);
-            Some(name.clone())
-        })
-        .collect();
-
-    // A single conjunction module ouptuts rx. It's inputs must all be high such that the signal low is sent to rx
-    assert_eq!(sources_to_rx.len(), 1);
-    let conj = &sources_to_rx[0];
-
-    let sources_to_conj: Vec<String> = modules
-        .modules
-        .iter()
-        .filter_map(|(name, (_module, outs))| {
-            if outs.iter().find(|n| *n == conj).is_none() {
-                return None;
-            }
-            Some(name.clone())
-        })
-        .collect();
-
-    println!("sources to conjunction : {sources_to_conj:?}");
-
-    let mut high_after = HashMap::new();
-
-    'outer: for i in std::iter::successors(Some(1usize), |n| Some(n + 1)) {
-        deque.push_back((Pulse::Low, "button".to_owned(), "broadcaster".to_owned()));
-
-        while let Some((pulse, source, dest)) = deque.pop_front() {
-            match pulse {
-                Pulse::Low => low += 1,
-                Pulse::High => high += 1,
-            };
-
-            if dest == *conj && pulse == Pulse::High {
-                if !high_after.contains_key(&source) {
-                    high_after.insert(source.clone(), i);
-                    println!("{source} ouptuts high after {i:?}");
-                }
-                if high_after.len() == sources_to_conj.len() {
-                    println!("Collected all required information");
-                    break 'outer;
-                }
-            }
-
-            if let Some((module, outputs)) = modules.modules.get_mut(&dest) {
-                if let Some(out_pulse) = module.execute(&source, pulse) {
-                    for out in &*outputs {
-                        deque.push_back((out_pulse, dest.clone(), out.to_string()));
-                    }
-                }
-            }
+    if mode == Mode::Counters {
+        let counters = day20::counters(&specs)?;
+        for c in &counters {
+            println!(
+                "{} -> {} ({} modules): high on press {}, then every {} presses",
+                c.entry,
+                c.exit,
+                c.modules.len(),
+                c.high_at,
+                c.period
+            );
         }
-    }
-
-    let mut result = 1;
-    for (_k, v) in high_after {
-        result = lcm(result, v);
-    }
-    println!("result {result}");
+        println!("rx: low on press {}", day20::first_alignment(&counters)?);
+        return Ok(());
+    }
+
+    if let Mode::Trace(path) = &mode {
+        let file = File::create(path).with_context(|| format!("Cannot create {path:?}"))?;
+        let mut writer =
+            TraceWriter::new(BufWriter::new(file), format.unwrap_or(TraceFormat::Jsonl));
+        Simulator::new(&specs).press_with(presses.unwrap_or(TRACE_PRESSES), |e| writer.record(e));
+        writer
+            .finish()
+            .with_context(|| format!("Cannot write {path:?}"))?;
+        return Ok(());
+    }
+
+    if let Mode::Replay(path) = &mode {
+        let file = File::open(path).with_context(|| format!("Cannot open {path:?}"))?;
+        let trace = day20::read_trace(file).with_context(|| format!("Cannot read {path:?}"))?;
+        day20::replay(&specs, &trace)?;
+        println!("{} pulses replayed identically", trace.len());
+        return Ok(());
+    }
+
+    // Edges are annotated with the pulses sent over the given number of presses
+    let mut simulator = Simulator::new(&specs);
+    let mut counts = PulseCounts::default();
+    if let Some(presses) = presses {
+        simulator.press_with(presses, |e| counts.record(e));
+    }
+    let counts = presses.map(|_| &counts);
+    if mode == Mode::Dot {
+        print!("{}", simulator.routing().to_dot(counts));
+    } else {
+        print!("{}", simulator.routing().to_mermaid(counts));
+    }
+    Ok(())
 }
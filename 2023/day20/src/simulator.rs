@@ -0,0 +1,146 @@
+use std::collections::VecDeque;
+
+use crate::{build_routing, ModuleRouting, ModuleSpec, Pulse};
+
+/// Pulse delivered to a module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Event<'a> {
+    /// Button press that started the pulse, counting from 1 since the simulator was built
+    pub press: usize,
+    /// Position of the pulse among the ones of the same press, the button's one being 0
+    pub seq: usize,
+    pub source: &'a str,
+    pub dest: &'a str,
+    pub pulse: Pulse,
+}
+
+/// State of every module along with the counters, see [`Simulator::snapshot`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Snapshot {
+    // Sorted by module name
+    modules: Vec<(String, Vec<u64>)>,
+    presses: usize,
+    low: usize,
+    high: usize,
+}
+
+type Observer<'a> = Box<dyn FnMut(&Event) + 'a>;
+
+impl Snapshot {
+    /// State of each module by name, leaving the counters out.
+    pub fn module_states(&self) -> &[(String, Vec<u64>)] {
+        &self.modules
+    }
+}
+
+/// Runs a module configuration one button press at a time.
+pub struct Simulator<'a> {
+    routing: ModuleRouting,
+    presses: usize,
+    low: usize,
+    high: usize,
+    observers: Vec<Observer<'a>>,
+}
+
+impl<'a> Simulator<'a> {
+    pub fn new(specs: &[ModuleSpec]) -> Self {
+        Self {
+            routing: build_routing(specs),
+            presses: 0,
+            low: 0,
+            high: 0,
+            observers: vec![],
+        }
+    }
+
+    pub fn routing(&self) -> &ModuleRouting {
+        &self.routing
+    }
+
+    /// Calls `observer` for every pulse sent by the following presses.
+    pub fn observe(&mut self, observer: impl FnMut(&Event) + 'a) {
+        self.observers.push(Box::new(observer));
+    }
+
+    pub fn press(&mut self, n: usize) {
+        self.press_with(n, |_| {});
+    }
+
+    /// Presses the button `n` times, calling `on_pulse` for every pulse after the observers.
+    pub fn press_with(&mut self, n: usize, mut on_pulse: impl FnMut(&Event)) {
+        let mut deque = VecDeque::new();
+        for _ in 0..n {
+            self.presses += 1;
+            deque.push_back((Pulse::Low, "button".to_owned(), "broadcaster".to_owned()));
+
+            let mut seq = 0;
+            while let Some((pulse, source, dest)) = deque.pop_front() {
+                match pulse {
+                    Pulse::Low => self.low += 1,
+                    Pulse::High => self.high += 1,
+                };
+
+                let event = Event {
+                    press: self.presses,
+                    seq,
+                    source: &source,
+                    dest: &dest,
+                    pulse,
+                };
+                for observer in &mut self.observers {
+                    observer(&event);
+                }
+                on_pulse(&event);
+                seq += 1;
+
+                if let Some((module, outputs)) = self.routing.modules.get_mut(&dest) {
+                    if let Some(out_pulse) = module.execute(&source, pulse) {
+                        for out in &*outputs {
+                            deque.push_back((out_pulse, dest.clone(), out.to_string()));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn presses(&self) -> usize {
+        self.presses
+    }
+
+    /// Number of low and high pulses sent so far.
+    pub fn counts(&self) -> (usize, usize) {
+        (self.low, self.high)
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        let mut modules: Vec<_> = self
+            .routing
+            .modules
+            .iter()
+            .map(|(name, (module, _))| (name.clone(), module.state()))
+            .collect();
+        modules.sort();
+        Snapshot {
+            modules,
+            presses: self.presses,
+            low: self.low,
+            high: self.high,
+        }
+    }
+
+    /// Brings the modules and counters back to a snapshot taken from the same configuration.
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        for (name, state) in &snapshot.modules {
+            self.routing
+                .modules
+                .get_mut(name)
+                .expect("Snapshot of the same modules")
+                .0
+                .restore(state);
+        }
+        self.presses = snapshot.presses;
+        self.low = snapshot.low;
+        self.high = snapshot.high;
+    }
+}
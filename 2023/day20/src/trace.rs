@@ -0,0 +1,271 @@
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Event, ModuleSpec, Pulse, Simulator};
+
+// Start of binary traces, JSONL ones start with `{`
+const MAGIC: &[u8; 5] = b"PULS1";
+// Record tags of binary traces
+const NAME: u8 = 0;
+const LOW: u8 = 1;
+const HIGH: u8 = 2;
+
+/// Owned copy of an [`Event`], as stored in a trace.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraceEvent {
+    pub press: usize,
+    pub seq: usize,
+    pub source: String,
+    pub dest: String,
+    pub pulse: Pulse,
+}
+
+impl From<&Event<'_>> for TraceEvent {
+    fn from(e: &Event) -> Self {
+        Self {
+            press: e.press,
+            seq: e.seq,
+            source: e.source.to_owned(),
+            dest: e.dest.to_owned(),
+            pulse: e.pulse,
+        }
+    }
+}
+
+impl std::fmt::Display for TraceEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let pulse = match self.pulse {
+            Pulse::Low => "low",
+            Pulse::High => "high",
+        };
+        write!(
+            f,
+            "press {} #{}: {} -{pulse}-> {}",
+            self.press, self.seq, self.source, self.dest
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceFormat {
+    /// One JSON object per line, easy to read and grep
+    Jsonl,
+    /// Module names written once and referred to by index, numbers as LEB128 varints
+    Binary,
+}
+
+/// Writes every event it is given to `out`. Meant to be fed from a [`Simulator`] observer, so
+/// errors are kept until [`TraceWriter::finish`].
+pub struct TraceWriter<W: Write> {
+    out: W,
+    format: TraceFormat,
+    // Index of the module names already written to a binary trace
+    names: HashMap<String, usize>,
+    error: Option<io::Error>,
+}
+
+impl<W: Write> TraceWriter<W> {
+    pub fn new(mut out: W, format: TraceFormat) -> Self {
+        let error = match format {
+            TraceFormat::Binary => out.write_all(MAGIC).err(),
+            TraceFormat::Jsonl => None,
+        };
+        Self {
+            out,
+            format,
+            names: HashMap::new(),
+            error,
+        }
+    }
+
+    pub fn record(&mut self, event: &Event) {
+        if self.error.is_none() {
+            self.error = self.write(event).err();
+        }
+    }
+
+    fn write(&mut self, event: &Event) -> io::Result<()> {
+        match self.format {
+            TraceFormat::Jsonl => {
+                serde_json::to_writer(&mut self.out, &TraceEvent::from(event))?;
+                self.out.write_all(b"\n")
+            }
+            TraceFormat::Binary => {
+                let source = self.name(event.source)?;
+                let dest = self.name(event.dest)?;
+                let tag = match event.pulse {
+                    Pulse::Low => LOW,
+                    Pulse::High => HIGH,
+                };
+                self.out.write_all(&[tag])?;
+                for value in [event.press, event.seq, source, dest] {
+                    write_varint(&mut self.out, value)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    // Index of `name`, written along with its index the first time it shows up
+    fn name(&mut self, name: &str) -> io::Result<usize> {
+        if let Some(idx) = self.names.get(name) {
+            return Ok(*idx);
+        }
+        let idx = self.names.len();
+        self.out.write_all(&[NAME])?;
+        write_varint(&mut self.out, name.len())?;
+        self.out.write_all(name.as_bytes())?;
+        self.names.insert(name.to_owned(), idx);
+        Ok(idx)
+    }
+
+    /// Flushes the trace, reporting the first error met while writing it.
+    pub fn finish(mut self) -> io::Result<W> {
+        if let Some(e) = self.error {
+            return Err(e);
+        }
+        self.out.flush()?;
+        Ok(self.out)
+    }
+}
+
+fn write_varint(out: &mut impl Write, mut value: usize) -> io::Result<()> {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            return out.write_all(&[byte]);
+        }
+        out.write_all(&[byte | 0x80])?;
+    }
+}
+
+fn invalid(msg: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.into())
+}
+
+// Reads a single byte, `None` at the end of the input
+fn read_byte(input: &mut impl Read) -> io::Result<Option<u8>> {
+    let mut byte = [0];
+    match input.read_exact(&mut byte) {
+        Ok(()) => Ok(Some(byte[0])),
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+fn read_varint(input: &mut impl Read) -> io::Result<usize> {
+    let mut value = 0;
+    for shift in (0..usize::BITS).step_by(7) {
+        let byte = read_byte(input)?.ok_or_else(|| invalid("truncated number"))?;
+        value |= ((byte & 0x7f) as usize) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(invalid("number too large"))
+}
+
+/// Reads a trace written by [`TraceWriter`], in either format.
+pub fn read_trace(input: impl Read) -> io::Result<Vec<TraceEvent>> {
+    let mut input = BufReader::new(input);
+    if !input.fill_buf()?.starts_with(MAGIC) {
+        return input
+            .lines()
+            .filter(|line| !line.as_ref().is_ok_and(|l| l.trim().is_empty()))
+            .map(|line| serde_json::from_str(&line?).map_err(|e| invalid(e.to_string())))
+            .collect();
+    }
+
+    input.consume(MAGIC.len());
+    let mut names = vec![];
+    let mut events = vec![];
+    while let Some(tag) = read_byte(&mut input)? {
+        match tag {
+            NAME => {
+                let mut name = vec![0; read_varint(&mut input)?];
+                input.read_exact(&mut name)?;
+                names.push(String::from_utf8(name).map_err(|e| invalid(e.to_string()))?);
+            }
+            LOW | HIGH => {
+                let [press, seq, source, dest] = [(); 4].map(|_| read_varint(&mut input));
+                let name = |idx: io::Result<usize>| -> io::Result<String> {
+                    let idx = idx?;
+                    names
+                        .get(idx)
+                        .cloned()
+                        .ok_or_else(|| invalid(format!("undefined module #{idx}")))
+                };
+                events.push(TraceEvent {
+                    press: press?,
+                    seq: seq?,
+                    source: name(source)?,
+                    dest: name(dest)?,
+                    pulse: if tag == LOW { Pulse::Low } else { Pulse::High },
+                });
+            }
+            t => return Err(invalid(format!("unknown record {t}"))),
+        }
+    }
+    Ok(events)
+}
+
+/// First difference between a recorded trace and a new simulation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    /// Position of the event in the trace
+    pub index: usize,
+    /// `None` when the simulation sent more pulses than recorded
+    pub expected: Option<TraceEvent>,
+    /// `None` when the simulation sent fewer pulses than recorded
+    pub actual: Option<TraceEvent>,
+}
+
+impl std::fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let show = |e: &Option<TraceEvent>| match e {
+            Some(e) => e.to_string(),
+            None => "nothing".to_owned(),
+        };
+        write!(
+            f,
+            "event {} differs: expected {}, got {}",
+            self.index,
+            show(&self.expected),
+            show(&self.actual)
+        )
+    }
+}
+
+impl std::error::Error for Mismatch {}
+
+/// Runs the modules from their initial state for as many presses as the trace covers, checking
+/// that they send the exact same pulses.
+pub fn replay(specs: &[ModuleSpec], trace: &[TraceEvent]) -> Result<(), Box<Mismatch>> {
+    let presses = trace.last().map_or(0, |e| e.press);
+    let mut simulator = Simulator::new(specs);
+    let mut index = 0;
+    let mut mismatch = None;
+    simulator.press_with(presses, |e| {
+        if mismatch.is_none() && trace.get(index).is_none_or(|t| *t != TraceEvent::from(e)) {
+            mismatch = Some(Mismatch {
+                index,
+                expected: trace.get(index).cloned(),
+                actual: Some(e.into()),
+            });
+        }
+        index += 1;
+    });
+
+    match mismatch {
+        Some(m) => Err(Box::new(m)),
+        None if index < trace.len() => Err(Box::new(Mismatch {
+            index,
+            expected: Some(trace[index].clone()),
+            actual: None,
+        })),
+        None => Ok(()),
+    }
+}
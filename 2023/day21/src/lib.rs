@@ -0,0 +1,407 @@
+use std::collections::VecDeque;
+
+use common::parse;
+use common::{Answer, Grid, ParseError, Pos, Solution};
+
+const NUM_STEPS: usize = 26501365;
+
+// Step counts up to this many map periods are counted directly
+const DIRECT_PERIODS: usize = 4;
+// Sample sizes tried before giving up on finding quadratic growth
+const MAX_SAMPLES: usize = 16;
+
+pub struct Map(Grid<char>);
+
+type Distances = Grid<Option<usize>>;
+
+impl Map {
+    fn start(&self) -> Pos {
+        self.0.find(&'S').unwrap()
+    }
+}
+
+// Steps from `start` to every plot of a single copy of the map, `None` for the ones out of reach
+fn distances(map: &Map, start: Pos) -> Distances {
+    let mut dists = Grid::new(map.0.rows(), map.0.cols(), None);
+    dists[start] = Some(0);
+    let mut queue = VecDeque::from([(start, 0)]);
+    while let Some((pos, dist)) = queue.pop_front() {
+        for next in map.0.neighbours4(pos) {
+            if map.0[next] != '#' && dists[next].is_none() {
+                dists[next] = Some(dist + 1);
+                queue.push_back((next, dist + 1));
+            }
+        }
+    }
+    dists
+}
+
+// Plots that can be ended on after exactly `steps` steps: the ones close enough and of the same
+// parity, as steps can be wasted by going back and forth
+fn count_within(dists: &Distances, steps: usize) -> usize {
+    dists
+        .iter()
+        .filter(|(_, d)| d.is_some_and(|d| d <= steps && d % 2 == steps % 2))
+        .count()
+}
+
+/// The map along with the steps to every plot of a copy of it from where walks enter it: the
+/// start, the middles of the edges and the corners. They are computed once and shared by every
+/// step count.
+pub struct Garden {
+    map: Map,
+    centre: Distances,
+    // From the middles of the left, right, top and bottom edges
+    tips: [Distances; 4],
+    // From the top left, top right, bottom left and bottom right corners
+    corners: [Distances; 4],
+}
+
+impl Garden {
+    pub fn new(map: Map) -> Self {
+        let (sr, sc) = map.start();
+        let (mr, mc) = map.0.max();
+        Self {
+            centre: distances(&map, (sr, sc)),
+            tips: [(sr, 0), (sr, mc), (0, sc), (mr, sc)].map(|p| distances(&map, p)),
+            corners: [(0, 0), (0, mc), (mr, 0), (mr, mc)].map(|p| distances(&map, p)),
+            map,
+        }
+    }
+}
+
+/// Reason the block formula can't be used for a map and step count.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Irregularity {
+    NotSquare {
+        rows: usize,
+        cols: usize,
+    },
+    /// The start isn't in the middle of an odd-sized map
+    OffCentre(Pos),
+    /// A rock blocks the row or the column of the start
+    BlockedCentre,
+    /// A rock blocks an edge of the map
+    BlockedBorder,
+    /// A rock blocks the diamond joining the middles of the edges
+    BlockedDiamond,
+    /// The steps don't end on the edge of a map copy
+    Remainder {
+        steps: usize,
+        size: usize,
+    },
+}
+
+impl std::fmt::Display for Irregularity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Irregularity::NotSquare { rows, cols } => write!(f, "the map is {rows}x{cols}"),
+            Irregularity::OffCentre((r, c)) => {
+                write!(f, "S at ({r}, {c}) isn't in the middle of the map")
+            }
+            Irregularity::BlockedCentre => write!(f, "rocks block the row or column of S"),
+            Irregularity::BlockedBorder => write!(f, "rocks block an edge of the map"),
+            Irregularity::BlockedDiamond => {
+                write!(
+                    f,
+                    "rocks block the diamond between the middles of the edges"
+                )
+            }
+            Irregularity::Remainder { steps, size } => write!(
+                f,
+                "{steps} steps don't end half a map ({}) after a multiple of {size}",
+                size / 2
+            ),
+        }
+    }
+}
+
+/// How a number of reachable plots was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    /// Walking the infinite map, for small step counts
+    Direct,
+    /// Adding up whole copies of the map, which only works on maps with clear lines through S
+    Blocks,
+    /// Fitting a quadratic to three step counts a map period apart, once its second difference
+    /// settled
+    Extrapolated(Irregularity),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Count {
+    pub plots: usize,
+    pub method: Method,
+}
+
+/// Neither the block formula nor quadratic extrapolation apply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unsolvable(pub Irregularity);
+
+impl std::fmt::Display for Unsolvable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}, and reachable plots don't grow quadratically over {MAX_SAMPLES} map periods",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for Unsolvable {}
+
+// Whether the plots reachable after `size / 2 + n * size` steps can be counted by whole map copies
+fn check_blocks(map: &Map, steps: usize) -> Result<(), Irregularity> {
+    let grid = &map.0;
+    let (rows, cols) = (grid.rows(), grid.cols());
+    if rows != cols {
+        return Err(Irregularity::NotSquare { rows, cols });
+    }
+    let start = map.start();
+    if rows % 2 == 0 || start != (rows / 2, cols / 2) {
+        return Err(Irregularity::OffCentre(start));
+    }
+    let clear = |mut cells: Box<dyn Iterator<Item = &char> + '_>| cells.all(|c| *c != '#');
+    if !clear(Box::new(grid.row(start.0).iter())) || !clear(Box::new(grid.column(start.1))) {
+        return Err(Irregularity::BlockedCentre);
+    }
+    let (mr, mc) = grid.max();
+    if ![grid.row(0), grid.row(mr)]
+        .iter()
+        .all(|r| clear(Box::new(r.iter())))
+        || ![0, mc].iter().all(|c| clear(Box::new(grid.column(*c))))
+    {
+        return Err(Irregularity::BlockedBorder);
+    }
+    if grid
+        .positions(&'#')
+        .any(|(r, c)| r.abs_diff(start.0) + c.abs_diff(start.1) == rows / 2)
+    {
+        return Err(Irregularity::BlockedDiamond);
+    }
+    if steps % rows != rows / 2 {
+        return Err(Irregularity::Remainder { steps, size: rows });
+    }
+    Ok(())
+}
+
+// Whole copies of the map reached at odd and even steps, the copies at the tips of the diamond
+// of reachable plots and the ones crossed by its edges
+fn count_blocks(garden: &Garden, steps: usize) -> usize {
+    let size = garden.map.0.rows();
+
+    // Copies an even number of copies away from the middle one end on plots of the parity of
+    // `steps`, the others on plots of the opposite parity, as the size of the map is odd
+    let n = steps / size;
+    let (same_blocks, flipped_blocks) = if n % 2 == 0 {
+        ((n - 1) * (n - 1), n * n)
+    } else {
+        (n * n, (n - 1) * (n - 1))
+    };
+
+    let parity = steps % 2;
+    let same = count_within(&garden.centre, size * 2 + parity);
+    let flipped = count_within(&garden.centre, size * 2 + 1 - parity);
+
+    let tips: usize = garden
+        .tips
+        .iter()
+        .map(|dists| count_within(dists, size - 1))
+        .sum();
+
+    // Each corner of the map leads into both a large and a small copy along the edges
+    let (num_large_blocks, num_small_blocks) = (steps / size - 1, steps / size);
+    let edges: usize = garden
+        .corners
+        .iter()
+        .map(|dists| {
+            count_within(dists, size * 3 / 2 - 1) * num_large_blocks
+                + count_within(dists, size / 2 - 1) * num_small_blocks
+        })
+        .sum();
+
+    same_blocks * same + flipped_blocks * flipped + tips + edges
+}
+
+// Steps from the start to every plot of the copies of the map around it, as far as walks of
+// `steps` steps go on the infinite map
+fn tiled_distances(map: &Map, steps: usize) -> Distances {
+    let (rows, cols) = (map.0.rows(), map.0.cols());
+    let (copies_r, copies_c) = (steps / rows + 1, steps / cols + 1);
+    let (tiled_rows, tiled_cols) = (rows * (copies_r * 2 + 1), cols * (copies_c * 2 + 1));
+    let cells = (0..tiled_rows * tiled_cols)
+        .map(|i| map.0[(i / tiled_cols % rows, i % tiled_cols % cols)])
+        .collect();
+    let tiled = Map(Grid::from_vec(tiled_rows, tiled_cols, cells));
+
+    let (sr, sc) = map.start();
+    distances(&tiled, (sr + copies_r * rows, sc + copies_c * cols))
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+impl Garden {
+    /// Plots reachable in exactly `steps` steps on the map repeated infinitely in every direction.
+    pub fn reachable(&self, steps: usize) -> Result<Count, Unsolvable> {
+        let map = &self.map;
+        let (rows, cols) = (map.0.rows(), map.0.cols());
+        let period = rows / gcd(rows, cols) * cols;
+
+        if steps <= DIRECT_PERIODS * period {
+            return Ok(Count {
+                plots: count_within(&tiled_distances(map, steps), steps),
+                method: Method::Direct,
+            });
+        }
+
+        let reason = match check_blocks(map, steps) {
+            Ok(()) => {
+                return Ok(Count {
+                    plots: count_blocks(self, steps),
+                    method: Method::Blocks,
+                })
+            }
+            Err(reason) => reason,
+        };
+
+        // Samples a period apart, until the last three second differences agree
+        let (target, remainder) = (steps / period, steps % period);
+        let mut samples: Vec<i128> = vec![];
+        // Distances as far as the samples needed at least, going twice as far when they run out
+        let mut reach = remainder + 4 * period;
+        let mut dists = tiled_distances(map, reach);
+        for n in 0..MAX_SAMPLES {
+            let sample_steps = remainder + n * period;
+            if sample_steps > reach {
+                reach = remainder + (n * 2).min(MAX_SAMPLES - 1) * period;
+                dists = tiled_distances(map, reach);
+            }
+            samples.push(count_within(&dists, sample_steps) as i128);
+            if n == target {
+                // Early samples can be before the growth settles, but they are exact
+                return Ok(Count {
+                    plots: samples[n] as usize,
+                    method: Method::Direct,
+                });
+            }
+
+            let k = samples.len();
+            let second = |i: usize| samples[i] - 2 * samples[i - 1] + samples[i - 2];
+            if k >= 5 && second(k - 1) == second(k - 2) && second(k - 2) == second(k - 3) {
+                // f(n0 + x) = a + x * (b - a) + x * (x - 1) / 2 * d2
+                let (n0, a, b) = (k as i128 - 3, samples[k - 3], samples[k - 2]);
+                let x = target as i128 - n0;
+                let plots = a + x * (b - a) + x * (x - 1) / 2 * second(k - 1);
+                return Ok(Count {
+                    plots: plots as usize,
+                    method: Method::Extrapolated(reason),
+                });
+            }
+        }
+        Err(Unsolvable(reason))
+    }
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input = Garden;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let grid = Grid::parse(input, ".#S")?;
+        parse::find_tile(input, 'S')?;
+        Ok(Garden::new(Map(grid)))
+    }
+
+    fn part1(garden: &Self::Input) -> Answer {
+        count_within(&garden.centre, 64).into()
+    }
+
+    fn part2(garden: &Self::Input) -> Answer {
+        match garden.reachable(NUM_STEPS) {
+            Ok(count) => count.plots.into(),
+            Err(e) => format!("cannot count the plots: {e}").into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "...........
+.....###.#.
+.###.##..#.
+..#.#...#..
+....#.#....
+.##..S####.
+.##..#...#.
+.......##..
+.##.#.####.
+.##..##.##.
+...........
+";
+
+    #[test]
+    fn counts_any_steps() {
+        let garden = Day21::parse(EXAMPLE).unwrap();
+        let dists = &garden.centre;
+        assert_eq!(count_within(dists, 6), 16);
+        assert_eq!(dists[(0, 0)], Some(10));
+
+        let counts: Vec<_> = [6, 10, 50, 100, 500, 1000, 5000]
+            .iter()
+            .map(|steps| garden.reachable(*steps).unwrap().plots)
+            .collect();
+        assert_eq!(counts, [16, 50, 1594, 6536, 167004, 668697, 16733044]);
+        assert_eq!(
+            garden.reachable(5000).unwrap().method,
+            Method::Extrapolated(Irregularity::BlockedCentre)
+        );
+    }
+
+    #[test]
+    fn blocks_match_walking() {
+        let map = Day21::parse(".......\n.#...#.\n..#....\n...S...\n....#..\n.#.....\n.......\n")
+            .unwrap();
+        let open = Day21::parse(".....\n.....\n..S..\n.....\n.....\n").unwrap();
+        // Both parities of steps, with an odd and an even number of map copies to the edge
+        for (map, steps) in [
+            (&map, 213),
+            (&map, 220),
+            (&map, 234),
+            (&open, 152),
+            (&open, 157),
+        ] {
+            let count = map.reachable(steps).unwrap();
+            assert_eq!(count.method, Method::Blocks);
+
+            assert_eq!(
+                count.plots,
+                count_within(&tiled_distances(&map.map, steps), steps),
+                "{steps} steps"
+            );
+        }
+
+        let steps = 213;
+        assert_eq!(
+            check_blocks(&map.map, steps + 1),
+            Err(Irregularity::Remainder {
+                steps: steps + 1,
+                size: 7
+            })
+        );
+
+        let diamond = Day21::parse(".....\n.#...\n..S..\n.....\n.....\n").unwrap();
+        assert_eq!(
+            check_blocks(&diamond.map, steps),
+            Err(Irregularity::BlockedDiamond)
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use std::collections::VecDeque;
+
+use common::parse;
+use common::{Answer, Grid, ParseError, Solution};
+
+pub type Coord = [usize; 3];
+
+pub type Brick = (Coord, Coord);
+
+/// Bricks once they have all fallen as far as they can, in input order, along with the bricks
+/// each of them rests on.
+pub struct Stack {
+    bricks: Vec<Brick>,
+    supports: Vec<Vec<usize>>,
+    supported_by: Vec<Vec<usize>>,
+}
+
+impl Stack {
+    /// Drops the bricks from the lowest one up, keeping the top of the stack and the brick it
+    /// belongs to for every (x, y) column.
+    pub fn settle(mut bricks: Vec<Brick>) -> Self {
+        let (max_x, max_y) = bricks
+            .iter()
+            .fold((0, 0), |(x, y), (_, [xe, ye, _])| (x.max(*xe), y.max(*ye)));
+        let mut tops: Grid<(usize, Option<usize>)> = Grid::new(max_y + 1, max_x + 1, (0, None));
+
+        let mut order: Vec<_> = (0..bricks.len()).collect();
+        order.sort_by_key(|i| bricks[*i].0[2]);
+
+        let mut supports = vec![vec![]; bricks.len()];
+        let mut supported_by = vec![vec![]; bricks.len()];
+        for i in order {
+            let ([xs, ys, zs], [xe, ye, ze]) = bricks[i];
+            let cells = || (ys..=ye).flat_map(move |y| (xs..=xe).map(move |x| (y, x)));
+
+            let floor = cells().map(|cell| tops[cell].0).max().unwrap_or(0);
+            for cell in cells() {
+                if let (z, Some(below)) = tops[cell] {
+                    if z == floor && !supported_by[i].contains(&below) {
+                        supported_by[i].push(below);
+                        supports[below].push(i);
+                    }
+                }
+            }
+
+            let height = ze - zs;
+            bricks[i].0[2] = floor + 1;
+            bricks[i].1[2] = floor + 1 + height;
+            for cell in cells() {
+                tops[cell] = (floor + 1 + height, Some(i));
+            }
+        }
+
+        Self {
+            bricks,
+            supports,
+            supported_by,
+        }
+    }
+
+    pub fn bricks(&self) -> &[Brick] {
+        &self.bricks
+    }
+
+    /// Bricks resting on brick `i`.
+    pub fn supports(&self, i: usize) -> &[usize] {
+        &self.supports[i]
+    }
+
+    /// Bricks brick `i` rests on, none when it is on the ground.
+    pub fn supported_by(&self, i: usize) -> &[usize] {
+        &self.supported_by[i]
+    }
+
+    /// Bricks that can be removed without any other one falling.
+    pub fn disintegrate_safe(&self) -> Vec<usize> {
+        (0..self.bricks.len())
+            .filter(|i| {
+                self.supports[*i]
+                    .iter()
+                    .all(|s| self.supported_by[*s].len() > 1)
+            })
+            .collect()
+    }
+
+    /// Bricks falling when brick `i` is removed, in the order they lose their last support.
+    pub fn chain_reaction(&self, i: usize) -> Vec<usize> {
+        self.remove(&[i])
+    }
+
+    /// Bricks falling when all of `removed` are taken out at once, in the order they lose their
+    /// last support.
+    pub fn remove(&self, removed: &[usize]) -> Vec<usize> {
+        let mut remaining: Vec<_> = self.supported_by.iter().map(|s| s.len()).collect();
+        let mut gone = vec![false; self.bricks.len()];
+        let mut queue = VecDeque::new();
+        for i in removed {
+            if !std::mem::replace(&mut gone[*i], true) {
+                queue.push_back(*i);
+            }
+        }
+
+        let mut falling = vec![];
+        while let Some(cur) = queue.pop_front() {
+            for above in &self.supports[cur] {
+                remaining[*above] -= 1;
+                if remaining[*above] == 0 && !gone[*above] {
+                    gone[*above] = true;
+                    falling.push(*above);
+                    queue.push_back(*above);
+                }
+            }
+        }
+        falling
+    }
+
+    /// Settled brick occupying the cube at `coord`.
+    pub fn brick_at(&self, coord: Coord) -> Option<usize> {
+        self.bricks
+            .iter()
+            .position(|(start, end)| (0..3).all(|a| (start[a]..=end[a]).contains(&coord[a])))
+    }
+}
+
+fn parse_brick(line: &str) -> Result<Brick, ParseError> {
+    let (start, end) = parse::split_once(line, "~")?;
+    let brick: Brick = (parse::array(start, ',')?, parse::array(end, ',')?);
+    if brick.0.iter().zip(brick.1).any(|(s, e)| *s > e) {
+        return Err(ParseError::new(
+            line,
+            "coordinates increasing from start to end",
+        ));
+    }
+    if brick.0[2] == 0 {
+        return Err(ParseError::new(line, "a brick above the ground"));
+    }
+    Ok(brick)
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input = Stack;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let bricks = input.lines().map(parse_brick).collect::<Result<_, _>>()?;
+        Ok(Stack::settle(bricks))
+    }
+
+    fn part1(stack: &Self::Input) -> Answer {
+        stack.disintegrate_safe().len().into()
+    }
+
+    fn part2(stack: &Self::Input) -> Answer {
+        (0..stack.bricks().len())
+            .map(|i| stack.chain_reaction(i).len())
+            .sum::<usize>()
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "1,0,1~1,2,1
+0,0,2~2,0,2
+0,2,3~2,2,3
+0,0,4~0,2,4
+2,0,5~2,2,5
+0,1,6~2,1,6
+1,1,8~1,1,9
+";
+
+    #[test]
+    fn settles_bricks() {
+        let stack = Day22::parse(EXAMPLE).unwrap();
+        assert_eq!(stack.bricks()[6], ([1, 1, 5], [1, 1, 6]));
+        assert_eq!(stack.supported_by(3), [1, 2]);
+        assert_eq!(stack.supports(0), [1, 2]);
+
+        assert_eq!(stack.disintegrate_safe(), [1, 2, 3, 4, 6]);
+        assert_eq!(stack.chain_reaction(0), [1, 2, 3, 4, 5, 6]);
+        assert_eq!(stack.chain_reaction(5), [6]);
+        assert!(stack.chain_reaction(1).is_empty());
+
+        assert!(stack.remove(&[1]).is_empty());
+        assert_eq!(stack.remove(&[1, 2]), [3, 4, 5, 6]);
+        assert_eq!(stack.remove(&[3, 4, 3]), [5, 6]);
+        assert_eq!(stack.brick_at([1, 1, 6]), Some(6));
+        assert_eq!(stack.brick_at([1, 1, 7]), None);
+    }
+}
@@ -0,0 +1,335 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use common::{Answer, Grid, ParseError, Pos, Solution};
+use rayon::prelude::*;
+
+// Visited junctions are kept in a u64 while searching
+const MAX_JUNCTIONS: usize = 64;
+// Indices of the start and end of the maze among the junctions
+const START: usize = 0;
+const END: usize = 1;
+// Junctions after the start at which hikes are split into independent searches
+const SPLIT_DEPTH: usize = 6;
+
+fn slope_direction(tile: char) -> Option<(isize, isize)> {
+    match tile {
+        '^' => Some((-1, 0)),
+        'v' => Some((1, 0)),
+        '<' => Some((0, -1)),
+        '>' => Some((0, 1)),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Map(Grid<char>);
+
+impl Map {
+    fn neighbours(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.0.neighbours4(pos).filter(|n| self.0[*n] != '#')
+    }
+
+    // Slopes can only be left in the direction they point to, and cannot be entered uphill
+    fn downhill(&self, from: Pos, to: Pos) -> bool {
+        let dir = (
+            to.0 as isize - from.0 as isize,
+            to.1 as isize - from.1 as isize,
+        );
+        slope_direction(self.0[from]).is_none_or(|d| d == dir)
+            && slope_direction(self.0[to]) != Some((-dir.0, -dir.1))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Edge {
+    to: usize,
+    steps: usize,
+    /// Whether the corridor can be walked this way when slopes are one-way
+    downhill: bool,
+}
+
+/// The maze compressed into the corridors between its junctions, the start and end included.
+#[derive(Debug, Clone)]
+pub struct Trails {
+    junctions: Vec<Pos>,
+    edges: Vec<Vec<Edge>>,
+}
+
+/// Longest walk from the start to the end of the maze.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hike {
+    pub steps: usize,
+    /// Junctions along the way, from the start to the end
+    pub junctions: Vec<Pos>,
+}
+
+impl Trails {
+    fn new(map: &Map, start: Pos, end: Pos) -> Self {
+        let mut junctions = vec![start, end];
+        junctions.extend(
+            map.0
+                .iter()
+                .filter(|(pos, tile)| **tile != '#' && map.neighbours(*pos).count() > 2)
+                .map(|(pos, _)| pos),
+        );
+        let index: HashMap<Pos, usize> =
+            junctions.iter().enumerate().map(|(i, p)| (*p, i)).collect();
+
+        // Every junction is walked from, so corridors end up recorded in both directions
+        let mut edges = vec![vec![]; junctions.len()];
+        for (from, pos) in junctions.iter().enumerate() {
+            for way in map.neighbours(*pos) {
+                let (mut prev, mut cur) = (*pos, way);
+                let mut steps = 1;
+                let mut downhill = map.downhill(prev, cur);
+                let to = loop {
+                    if let Some(to) = index.get(&cur) {
+                        break Some(*to);
+                    }
+                    // Anything but a junction leads to a single new tile, or none at a dead end
+                    let Some(next) = map.neighbours(cur).find(|n| *n != prev) else {
+                        break None;
+                    };
+                    downhill &= map.downhill(cur, next);
+                    (prev, cur) = (cur, next);
+                    steps += 1;
+                };
+                if let Some(to) = to.filter(|to| *to != from) {
+                    edges[from].push(Edge {
+                        to,
+                        steps,
+                        downhill,
+                    });
+                }
+            }
+        }
+        Self { junctions, edges }
+    }
+
+    pub fn junctions(&self) -> usize {
+        self.junctions.len()
+    }
+
+    /// Longest hike never going through a junction twice, walking slopes downhill only when
+    /// `slopes` is set. `None` when the end cannot be reached.
+    ///
+    /// The hikes are split after their first few junctions and the rest is searched in parallel.
+    /// Ties go to the first hike in the order corridors are listed, whatever the timing.
+    pub fn longest(&self, slopes: bool) -> Option<Hike> {
+        let mut longest_in = vec![0; self.junctions.len()];
+        for edges in &self.edges {
+            for edge in edges.iter().filter(|e| e.downhill || !slopes) {
+                longest_in[edge.to] = longest_in[edge.to].max(edge.steps);
+            }
+        }
+        let remaining = longest_in.iter().sum::<usize>() - longest_in[START];
+        let search = Search {
+            trails: self,
+            slopes,
+            longest_in,
+            best: AtomicUsize::new(0),
+        };
+
+        let mut prefixes = vec![];
+        search.split(
+            &mut Partial {
+                path: vec![START],
+                visited: 1 << START,
+                steps: 0,
+                remaining,
+            },
+            &mut prefixes,
+        );
+        let results: Vec<_> = prefixes
+            .into_par_iter()
+            .map(|mut prefix| {
+                let mut best = None;
+                search.solve(&mut prefix, &mut best);
+                best
+            })
+            .collect();
+
+        let mut best: Option<(usize, Vec<usize>)> = None;
+        for (steps, path) in results.into_iter().flatten() {
+            if best.as_ref().is_none_or(|(b, _)| steps > *b) {
+                best = Some((steps, path));
+            }
+        }
+        best.map(|(steps, path)| Hike {
+            steps,
+            junctions: path.iter().map(|j| self.junctions[*j]).collect(),
+        })
+    }
+}
+
+// Hike from the start to the last junction of `path`
+#[derive(Debug, Clone)]
+struct Partial {
+    path: Vec<usize>,
+    visited: u64,
+    steps: usize,
+    /// Most steps the rest of the hike could add
+    remaining: usize,
+}
+
+struct Search<'a> {
+    trails: &'a Trails,
+    slopes: bool,
+    /// Longest corridor leading to each junction, the most that going there can add to a hike
+    longest_in: Vec<usize>,
+    /// Longest hike found by any thread so far
+    best: AtomicUsize,
+}
+
+impl<'a> Search<'a> {
+    // Corridors leading out of the last junction of `partial` to junctions it hasn't been through
+    fn next(&self, partial: &Partial) -> impl Iterator<Item = &'a Edge> + 'a {
+        let (node, visited, slopes) = (*partial.path.last().unwrap(), partial.visited, self.slopes);
+        self.trails.edges[node]
+            .iter()
+            .filter(move |e| visited & (1 << e.to) == 0 && (e.downhill || !slopes))
+    }
+
+    // Goes down `edge` for the duration of `f`
+    fn walk<T>(&self, partial: &mut Partial, edge: &Edge, f: impl FnOnce(&mut Partial) -> T) -> T {
+        partial.path.push(edge.to);
+        partial.visited |= 1 << edge.to;
+        partial.steps += edge.steps;
+        partial.remaining -= self.longest_in[edge.to];
+        let result = f(partial);
+        partial.path.pop();
+        partial.visited &= !(1 << edge.to);
+        partial.steps -= edge.steps;
+        partial.remaining += self.longest_in[edge.to];
+        result
+    }
+
+    // Hikes going through SPLIT_DEPTH junctions, or ending before that
+    fn split(&self, partial: &mut Partial, out: &mut Vec<Partial>) {
+        if partial.path.len() > SPLIT_DEPTH || partial.path.last() == Some(&END) {
+            out.push(partial.clone());
+            return;
+        }
+        for edge in self.next(partial) {
+            self.walk(partial, edge, |p| self.split(p, out));
+        }
+    }
+
+    // Only hikes that can't even match the best one are cut, so the first of the longest hikes
+    // below `partial` is always found
+    fn solve(&self, partial: &mut Partial, best: &mut Option<(usize, Vec<usize>)>) {
+        if partial.steps + partial.remaining < self.best.load(Ordering::Relaxed) {
+            return;
+        }
+        if partial.path.last() == Some(&END) {
+            if best.as_ref().is_none_or(|(b, _)| partial.steps > *b) {
+                *best = Some((partial.steps, partial.path.clone()));
+                self.best.fetch_max(partial.steps, Ordering::Relaxed);
+            }
+            return;
+        }
+        for edge in self.next(partial) {
+            self.walk(partial, edge, |p| self.solve(p, best));
+        }
+    }
+}
+
+fn edge_tile(grid: &Grid<char>, row: usize, input: &str, what: &str) -> Result<Pos, ParseError> {
+    match grid.row(row).iter().position(|c| *c == '.') {
+        Some(col) => Ok((row, col)),
+        None => Err(ParseError::after(input, format!("a path tile {what}"))),
+    }
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Input = Trails;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let map = Map(Grid::parse(input, "#.^v<>")?);
+        let start = edge_tile(&map.0, 0, input, "in the first row")?;
+        let end = edge_tile(&map.0, map.0.rows() - 1, input, "in the last row")?;
+
+        let trails = Trails::new(&map, start, end);
+        if trails.junctions() > MAX_JUNCTIONS {
+            return Err(ParseError::after(
+                input,
+                format!(
+                    "at most {MAX_JUNCTIONS} junctions, found {}",
+                    trails.junctions()
+                ),
+            ));
+        }
+        Ok(trails)
+    }
+
+    fn part1(trails: &Self::Input) -> Answer {
+        match trails.longest(true) {
+            Some(hike) => hike.steps.into(),
+            None => "no downhill hike reaches the end".to_owned().into(),
+        }
+    }
+
+    fn part2(trails: &Self::Input) -> Answer {
+        match trails.longest(false) {
+            Some(hike) => hike.steps.into(),
+            None => "no hike reaches the end".to_owned().into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Two corridors join the same junctions, the longer one going up a slope
+    const MAZE: &str = "#.#######
+#.......#
+#.#####.#
+#.#.<.#.#
+#.#.#.#.#
+#...#...#
+#######.#
+";
+
+    #[test]
+    fn longest_hikes() {
+        let trails = Day23::parse(MAZE).unwrap();
+        assert_eq!(trails.junctions(), 4);
+
+        let downhill = trails.longest(true).unwrap();
+        assert_eq!(downhill.steps, 12);
+        assert_eq!(downhill.junctions, [(0, 1), (1, 1), (5, 7), (6, 7)]);
+        assert_eq!(trails.longest(false).unwrap().steps, 16);
+    }
+
+    #[test]
+    fn uphill_only() {
+        let trails = Day23::parse("#.#\n#^#\n#.#\n").unwrap();
+        assert_eq!(
+            Day23::part1(&trails),
+            Answer::from("no downhill hike reaches the end".to_owned())
+        );
+        assert_eq!(Day23::part2(&trails), Answer::from(2_usize));
+    }
+
+    #[test]
+    fn ties_are_deterministic() {
+        // Every hike going down the slopes is 10 steps long
+        let trails =
+            Day23::parse("#.#####\n#.>.>.#\n#v#v#v#\n#.>.>.#\n#v#v#v#\n#.>.>.#\n#####.#\n")
+                .unwrap();
+        let first = [(0, 1), (1, 1), (3, 1), (5, 3), (5, 5), (6, 5)];
+        for threads in 1..=4 {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+            let hike = pool.install(|| trails.longest(true)).unwrap();
+            assert_eq!((hike.steps, &hike.junctions[..]), (10, &first[..]));
+        }
+        assert_eq!(trails.longest(false).unwrap().steps, 18);
+    }
+}
@@ -0,0 +1,161 @@
+use std::cmp::Ordering;
+
+/// Signed integer of any size, just enough for fraction-free elimination: products, differences
+/// and divisions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BigInt {
+    negative: bool,
+    // Little-endian base 2^32 digits, without leading zeros
+    digits: Vec<u32>,
+}
+
+fn trim(mut digits: Vec<u32>) -> Vec<u32> {
+    while digits.last() == Some(&0) {
+        digits.pop();
+    }
+    digits
+}
+
+fn cmp_digits(a: &[u32], b: &[u32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_digits(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut sum = Vec::with_capacity(a.len().max(b.len()) + 1);
+    let mut carry = 0;
+    for i in 0..a.len().max(b.len()) {
+        let s = u64::from(*a.get(i).unwrap_or(&0)) + u64::from(*b.get(i).unwrap_or(&0)) + carry;
+        sum.push(s as u32);
+        carry = s >> 32;
+    }
+    sum.push(carry as u32);
+    trim(sum)
+}
+
+// `a - b` for `a >= b`
+fn sub_digits(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut diff = Vec::with_capacity(a.len());
+    let mut borrow = 0;
+    for (i, d) in a.iter().enumerate() {
+        let s = i64::from(*d) - i64::from(*b.get(i).unwrap_or(&0)) - borrow;
+        diff.push(s.rem_euclid(1 << 32) as u32);
+        borrow = i64::from(s < 0);
+    }
+    trim(diff)
+}
+
+fn mul_digits(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut product = vec![0u32; a.len() + b.len()];
+    for (i, x) in a.iter().enumerate() {
+        let mut carry = 0;
+        for (j, y) in b.iter().enumerate() {
+            let p = u64::from(*x) * u64::from(*y) + u64::from(product[i + j]) + carry;
+            product[i + j] = p as u32;
+            carry = p >> 32;
+        }
+        product[i + b.len()] = carry as u32;
+    }
+    trim(product)
+}
+
+// Binary long division, `(a / b, a % b)`
+fn div_rem_digits(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    let mut quotient = vec![0u32; a.len()];
+    let mut rem: Vec<u32> = Vec::new();
+    for bit in (0..a.len() * 32).rev() {
+        rem = add_digits(&rem, &rem);
+        if a[bit / 32] >> (bit % 32) & 1 == 1 {
+            rem = add_digits(&rem, &[1]);
+        }
+        if cmp_digits(&rem, b) != Ordering::Less {
+            rem = sub_digits(&rem, b);
+            quotient[bit / 32] |= 1 << (bit % 32);
+        }
+    }
+    (trim(quotient), rem)
+}
+
+impl BigInt {
+    fn new(negative: bool, digits: Vec<u32>) -> Self {
+        let digits = trim(digits);
+        Self {
+            negative: negative && !digits.is_empty(),
+            digits,
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    /// Quotient and remainder of the division truncated towards zero. Panics when dividing by
+    /// zero.
+    pub fn div_rem(&self, divisor: &BigInt) -> (BigInt, BigInt) {
+        assert!(!divisor.is_zero(), "BigInt division by zero");
+        let (q, r) = div_rem_digits(&self.digits, &divisor.digits);
+        (
+            BigInt::new(self.negative != divisor.negative, q),
+            BigInt::new(self.negative, r),
+        )
+    }
+}
+
+impl From<i128> for BigInt {
+    fn from(value: i128) -> Self {
+        let mut abs = value.unsigned_abs();
+        let mut digits = Vec::new();
+        while abs > 0 {
+            digits.push(abs as u32);
+            abs >>= 32;
+        }
+        BigInt::new(value < 0, digits)
+    }
+}
+
+impl TryFrom<&BigInt> for i128 {
+    type Error = ();
+
+    fn try_from(value: &BigInt) -> Result<Self, Self::Error> {
+        if value.digits.len() > 4 {
+            return Err(());
+        }
+        let abs = value
+            .digits
+            .iter()
+            .rev()
+            .fold(0u128, |acc, d| acc << 32 | u128::from(*d));
+        if value.negative {
+            0i128.checked_sub_unsigned(abs).ok_or(())
+        } else {
+            i128::try_from(abs).map_err(|_| ())
+        }
+    }
+}
+
+impl std::ops::Mul for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, rhs: &BigInt) -> BigInt {
+        BigInt::new(
+            self.negative != rhs.negative,
+            mul_digits(&self.digits, &rhs.digits),
+        )
+    }
+}
+
+impl std::ops::Sub for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, rhs: &BigInt) -> BigInt {
+        if self.negative != rhs.negative {
+            // Same as adding the magnitudes, keeping the sign of `self`
+            return BigInt::new(self.negative, add_digits(&self.digits, &rhs.digits));
+        }
+        match cmp_digits(&self.digits, &rhs.digits) {
+            Ordering::Less => BigInt::new(!self.negative, sub_digits(&rhs.digits, &self.digits)),
+            _ => BigInt::new(self.negative, sub_digits(&self.digits, &rhs.digits)),
+        }
+    }
+}
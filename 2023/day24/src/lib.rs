@@ -0,0 +1,378 @@
+use common::parse;
+use common::{Answer, ParseError, Solution};
+
+mod bigint;
+mod linear;
+mod ratio;
+
+pub use linear::solve_integer;
+pub use ratio::Ratio;
+
+type Coord = [i64; 3];
+
+const TEST_AREA: (i64, i64) = (200000000000000, 400000000000000);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hailstone {
+    pub position: Coord,
+    pub velocity: Coord,
+}
+
+/// Pair of axes the paths of the hailstones are projected on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axes {
+    XY,
+    XZ,
+    YZ,
+}
+
+impl Axes {
+    fn indices(self) -> (usize, usize) {
+        match self {
+            Axes::XY => (0, 1),
+            Axes::XZ => (0, 2),
+            Axes::YZ => (1, 2),
+        }
+    }
+
+    fn names(self) -> (char, char) {
+        match self {
+            Axes::XY => ('x', 'y'),
+            Axes::XZ => ('x', 'z'),
+            Axes::YZ => ('y', 'z'),
+        }
+    }
+}
+
+impl std::str::FromStr for Axes {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "xy" => Ok(Axes::XY),
+            "xz" => Ok(Axes::XZ),
+            "yz" => Ok(Axes::YZ),
+            a => anyhow::bail!("Unknown axes {a:?}, expected xy, xz or yz"),
+        }
+    }
+}
+
+/// Where and when crossings of the hailstones' paths are counted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TestArea {
+    /// Inclusive bounds of both coordinates
+    pub bounds: (i64, i64),
+    pub axes: Axes,
+    /// Inclusive bounds of the times both hailstones get to the crossing, any future time when
+    /// `None`
+    pub window: Option<(i64, i64)>,
+}
+
+impl Default for TestArea {
+    fn default() -> Self {
+        Self {
+            bounds: TEST_AREA,
+            axes: Axes::XY,
+            window: None,
+        }
+    }
+}
+
+/// Point where the paths of two hailstones cross, and when each of them is there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Crossing {
+    /// Coordinates along the two axes the paths are projected on
+    pub point: (Ratio, Ratio),
+    pub times: (Ratio, Ratio),
+}
+
+/// Crossing of the paths of `a` and `b` projected on `axes`, `None` when they are parallel.
+pub fn crossing(a: &Hailstone, b: &Hailstone, axes: Axes) -> Option<Crossing> {
+    // a.p + t * a.v = b.p + s * b.v, solved for t and s with Cramer's rule
+    let (i, j) = axes.indices();
+    let (pu, pv): (i128, i128) = (a.position[i].into(), a.position[j].into());
+    let (au, av): (i128, i128) = (a.velocity[i].into(), a.velocity[j].into());
+    let (bu, bv): (i128, i128) = (b.velocity[i].into(), b.velocity[j].into());
+    let du = i128::from(b.position[i]) - pu;
+    let dv = i128::from(b.position[j]) - pv;
+
+    let det = bu * av - au * bv;
+    if det == 0 {
+        return None;
+    }
+    let t = bu * dv - bv * du;
+    let s = au * dv - av * du;
+    Some(Crossing {
+        point: (
+            Ratio::new(pu * det + t * au, det),
+            Ratio::new(pv * det + t * av, det),
+        ),
+        times: (Ratio::new(t, det), Ratio::new(s, det)),
+    })
+}
+
+/// What happens to the paths of a pair of hailstones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Report {
+    Parallel,
+    /// The paths crossed in the past of the first hailstone, the second one, or both
+    Past {
+        first: bool,
+        second: bool,
+    },
+    /// The hailstones get to the crossing outside of the time window
+    OutsideWindow(Crossing),
+    OutsideArea(Crossing),
+    Inside(Crossing),
+}
+
+impl Report {
+    pub fn for_pair(a: &Hailstone, b: &Hailstone, area: &TestArea) -> Self {
+        let Some(c) = crossing(a, b, area.axes) else {
+            return Report::Parallel;
+        };
+        let (first, second) = (c.times.0.is_negative(), c.times.1.is_negative());
+        if first || second {
+            return Report::Past { first, second };
+        }
+        let within =
+            |(low, high): (i64, i64), v: Ratio| (Ratio::from(low)..=Ratio::from(high)).contains(&v);
+        if let Some(window) = area.window {
+            if !within(window, c.times.0) || !within(window, c.times.1) {
+                return Report::OutsideWindow(c);
+            }
+        }
+        if !within(area.bounds, c.point.0) || !within(area.bounds, c.point.1) {
+            return Report::OutsideArea(c);
+        }
+        Report::Inside(c)
+    }
+
+    /// Describes the report the way the puzzle does, with `axes` naming the coordinates.
+    pub fn describe(&self, axes: Axes) -> String {
+        let (u, v) = axes.names();
+        let at = |c: &Crossing| format!("at {u}={}, {v}={}", c.point.0, c.point.1);
+        match self {
+            Report::Parallel => "paths are parallel, they never intersect".to_owned(),
+            Report::Past {
+                first: true,
+                second: true,
+            } => "paths crossed in the past for both hailstones".to_owned(),
+            Report::Past { first, .. } => format!(
+                "paths crossed in the past for hailstone {}",
+                if *first { "A" } else { "B" }
+            ),
+            Report::OutsideWindow(c) => format!(
+                "paths cross outside the time window ({}, at times {} and {})",
+                at(c),
+                c.times.0,
+                c.times.1
+            ),
+            Report::OutsideArea(c) => format!("paths cross outside the test area ({})", at(c)),
+            Report::Inside(c) => format!("paths cross inside the test area ({})", at(c)),
+        }
+    }
+}
+
+/// Report for every pair of hailstones, by their indices.
+pub fn reports(hail: &[Hailstone], area: &TestArea) -> Vec<(usize, usize, Report)> {
+    let mut reports = vec![];
+    for (i, a) in hail.iter().enumerate() {
+        for (j, b) in hail.iter().enumerate().skip(i + 1) {
+            reports.push((i, j, Report::for_pair(a, b, area)));
+        }
+    }
+    reports
+}
+
+/// Pairs of hailstones whose paths cross inside the test area.
+pub fn count_crossings(hail: &[Hailstone], area: &TestArea) -> usize {
+    reports(hail, area)
+        .iter()
+        .filter(|(_, _, r)| matches!(r, Report::Inside(_)))
+        .count()
+}
+
+// Both `a` and `b` lose the P×V term of the rock, so each pair of hailstones gives 3 linear
+// equations on the position P and velocity V of the rock:
+// P×(b.v - a.v) + (b.p - a.p)×V = b.p×b.v - a.p×a.v
+fn pair_equations(a: &Hailstone, b: &Hailstone) -> [([i128; 6], i128); 3] {
+    let (ap, av) = (a.position.map(i128::from), a.velocity.map(i128::from));
+    let (bp, bv) = (b.position.map(i128::from), b.velocity.map(i128::from));
+    let dv: [i128; 3] = std::array::from_fn(|k| bv[k] - av[k]);
+    let dp: [i128; 3] = std::array::from_fn(|k| bp[k] - ap[k]);
+    let cross = |u: [i128; 3], w: [i128; 3]| {
+        [
+            u[1] * w[2] - u[2] * w[1],
+            u[2] * w[0] - u[0] * w[2],
+            u[0] * w[1] - u[1] * w[0],
+        ]
+    };
+    let (rb, ra) = (cross(bp, bv), cross(ap, av));
+    [
+        ([0, dv[2], -dv[1], 0, -dp[2], dp[1]], rb[0] - ra[0]),
+        ([-dv[2], 0, dv[0], dp[2], 0, -dp[0]], rb[1] - ra[1]),
+        ([dv[1], -dv[0], 0, -dp[1], dp[0], 0], rb[2] - ra[2]),
+    ]
+}
+
+// Whether `rock` meets `hailstone` at a whole, non-negative time
+fn hits(rock: &Hailstone, hailstone: &Hailstone) -> bool {
+    let gap: [i64; 3] = std::array::from_fn(|k| rock.position[k] - hailstone.position[k]);
+    let closing: [i64; 3] = std::array::from_fn(|k| hailstone.velocity[k] - rock.velocity[k]);
+    let Some(k) = (0..3).find(|k| closing[*k] != 0) else {
+        return gap == [0; 3];
+    };
+    if gap[k] % closing[k] != 0 {
+        return false;
+    }
+    let t = gap[k] / closing[k];
+    t >= 0 && (0..3).all(|k| gap[k] == t * closing[k])
+}
+
+/// Rock throw hitting every hailstone at whole times, from three hailstones whose equations are
+/// independent.
+pub fn rock_throw(hail: &[Hailstone]) -> Option<Hailstone> {
+    let solution = hail.windows(3).find_map(|w| {
+        let (matrix, rhs): (Vec<_>, Vec<_>) = pair_equations(&w[0], &w[1])
+            .into_iter()
+            .chain(pair_equations(&w[0], &w[2]))
+            .map(|(row, b)| (row.to_vec(), b))
+            .unzip();
+        solve_integer(&matrix, &rhs)
+    })?;
+
+    let values: Vec<i64> = solution
+        .iter()
+        .map(|v| i64::try_from(*v).ok())
+        .collect::<Option<_>>()?;
+    let rock = Hailstone {
+        position: [values[0], values[1], values[2]],
+        velocity: [values[3], values[4], values[5]],
+    };
+    hail.iter().all(|h| hits(&rock, h)).then_some(rock)
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Input = Vec<Hailstone>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input
+            .lines()
+            .map(|line| {
+                let (position, velocity) = parse::split_once(line, "@")?;
+                Ok(Hailstone {
+                    position: parse::array(position, ',')?,
+                    velocity: parse::array(velocity, ',')?,
+                })
+            })
+            .collect()
+    }
+
+    fn part1(hail: &Self::Input) -> Answer {
+        count_crossings(hail, &TestArea::default()).into()
+    }
+
+    fn part2(hail: &Self::Input) -> Answer {
+        match rock_throw(hail) {
+            Some(rock) => rock.position.iter().sum::<i64>().into(),
+            None => "no rock throw hits every hailstone".to_owned().into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "19, 13, 30 @ -2,  1, -2
+18, 19, 22 @ -1, -1, -2
+20, 25, 34 @ -2, -2, -4
+12, 31, 28 @ -1, -2, -1
+20, 19, 15 @  1, -5, -3
+";
+
+    #[test]
+    fn crossings() {
+        let hail = Day24::parse(EXAMPLE).unwrap();
+        let c = crossing(&hail[0], &hail[1], Axes::XY).unwrap();
+        assert_eq!(c.point, (Ratio::new(43, 3), Ratio::new(46, 3)));
+        assert_eq!(c.times, (Ratio::new(7, 3), Ratio::new(11, 3)));
+
+        let mut area = TestArea {
+            bounds: (7, 27),
+            ..TestArea::default()
+        };
+        let kinds: Vec<_> = reports(&hail, &area)
+            .iter()
+            .map(|(_, _, r)| match r {
+                Report::Parallel => "parallel",
+                Report::Past { .. } => "past",
+                Report::OutsideWindow(_) => "window",
+                Report::OutsideArea(_) => "outside",
+                Report::Inside(_) => "inside",
+            })
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                "inside", "inside", "outside", "past", "parallel", "outside", "past", "outside",
+                "past", "past"
+            ]
+        );
+        assert_eq!(
+            reports(&hail, &area)[9].2,
+            Report::Past {
+                first: true,
+                second: true
+            }
+        );
+        assert_eq!(count_crossings(&hail, &area), 2);
+
+        // The first pair crosses after 7/3 and 11/3 nanoseconds, the second one after 11/3 and 25/6
+        area.window = Some((0, 4));
+        assert_eq!(count_crossings(&hail, &area), 1);
+        area.axes = Axes::YZ;
+        area.window = None;
+        assert_eq!(
+            Report::for_pair(&hail[0], &hail[1], &area),
+            Report::Inside(Crossing {
+                point: (18.into(), 20.into()),
+                times: (5.into(), 1.into()),
+            })
+        );
+    }
+
+    #[test]
+    fn throws_rock() {
+        let hail = Day24::parse(EXAMPLE).unwrap();
+        let rock = rock_throw(&hail).unwrap();
+        assert_eq!(rock.position, [24, 13, 10]);
+        assert_eq!(rock.velocity, [-3, 1, 2]);
+
+        let mut missed = hail.clone();
+        missed[4].velocity[2] += 1;
+        assert_eq!(rock_throw(&missed), None);
+    }
+
+    #[test]
+    fn solves_exactly() {
+        let matrix = [vec![2, 1], vec![1, 3]];
+        assert_eq!(solve_integer(&matrix, &[5, 10]), Some(vec![1, 3]));
+        // x = 3/5, y = 4/5
+        assert_eq!(solve_integer(&matrix, &[2, 3]), None);
+        assert_eq!(solve_integer(&[vec![2, 4], vec![1, 2]], &[2, 1]), None);
+
+        // Minors reach 2^190 before dividing back down
+        let big = 1 << 62;
+        let matrix = [vec![big, 1, 0], vec![1, big, 1], vec![0, 1, big]];
+        let x = [-7, big, 3];
+        let rhs: Vec<i128> = matrix
+            .iter()
+            .map(|row| row.iter().zip(x).map(|(a, b)| a * b).sum())
+            .collect();
+        assert_eq!(solve_integer(&matrix, &rhs), Some(x.to_vec()));
+    }
+}
@@ -0,0 +1,43 @@
+// Exact integer solutions of linear systems by fraction-free (Bareiss) Gauss-Jordan elimination.
+// Every entry stays an integer, the determinant of a minor of the system, and each division is
+// exact. With coordinates around 4e14 these minors go well past i128, so they are `BigInt`s.
+
+use crate::bigint::BigInt;
+
+/// Solution of `matrix * x = rhs` for a square system of integers. `None` when the system is
+/// singular, or when its solution isn't made of integers fitting in `i128`.
+pub fn solve_integer(matrix: &[Vec<i128>], rhs: &[i128]) -> Option<Vec<i128>> {
+    let n = rhs.len();
+    let mut rows: Vec<Vec<BigInt>> = matrix
+        .iter()
+        .zip(rhs)
+        .map(|(row, b)| row.iter().chain([b]).map(|v| BigInt::from(*v)).collect())
+        .collect();
+
+    let mut previous = BigInt::from(1);
+    for col in 0..n {
+        let pivot = (col..n).find(|r| !rows[*r][col].is_zero())?;
+        rows.swap(col, pivot);
+        let pivot_row = rows[col].clone();
+        for (r, row) in rows.iter_mut().enumerate() {
+            if r == col {
+                continue;
+            }
+            // Entries left of `col` are 0 but on the diagonal, which becomes the new pivot
+            for k in (0..=n).filter(|k| *k != col) {
+                let cross = &(&pivot_row[col] * &row[k]) - &(&row[col] * &pivot_row[k]);
+                row[k] = cross.div_rem(&previous).0;
+            }
+            row[col] = BigInt::from(0);
+        }
+        previous = pivot_row[col].clone();
+    }
+
+    // Every diagonal entry is now the determinant, the last pivot
+    rows.iter()
+        .map(|row| {
+            let (x, rem) = row[n].div_rem(&previous);
+            rem.is_zero().then(|| i128::try_from(&x).ok()).flatten()
+        })
+        .collect()
+}
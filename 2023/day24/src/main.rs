@@ -1,76 +1,62 @@
-use itertools::Itertools;
+use std::io::Read;
 
-type Coord = [isize; 3];
+use anyhow::{anyhow, bail, Context};
+use common::Solution;
+use day24::{Report, TestArea};
 
-fn time_for_point((x, _): (f64, f64), ([bx, _, ..], [ax, _, ..]): &(Coord, Coord)) -> f64 {
-    // x = a * t + b
-    // (x - b) / a = t
-    let t = (x - *bx as f64) / (*ax as f64);
-    t
-}
-
-const TEST_AREA: (f64, f64) = (200000000000000.0, 400000000000000.0);
+const USAGE: &str =
+    "usage: day24 [--area <low>..<high>] [--axes xy|xz|yz] [--window <from>..<to>] [--pairs] < input";
 
-fn main() {
-    let input: Vec<(Coord, Coord)> = std::io::stdin()
-        .lines()
-        .map(|line| {
-            let line = line.unwrap();
-            line.split("@")
-                .map(|s| {
-                    s.trim()
-                        .split(",")
-                        .map(|s| s.trim().parse().unwrap())
-                        .collect_vec()
-                        .try_into()
-                        .unwrap()
-                })
-                .collect_tuple()
-                .unwrap()
-        })
-        .collect_vec();
+fn range(arg: &str, value: &str) -> anyhow::Result<(i64, i64)> {
+    let (low, high) = value
+        .split_once("..")
+        .ok_or(anyhow!("Invalid value for {arg}, expected <low>..<high>"))?;
+    let parse = |v: &str| {
+        v.trim()
+            .parse::<i64>()
+            .with_context(|| format!("Invalid value for {arg}"))
+    };
+    let (low, high) = (parse(low)?, parse(high)?);
+    if low > high {
+        bail!("Empty range for {arg}: {low} is above {high}");
+    }
+    Ok((low, high))
+}
 
-    // y = a1 * x + b1
-    // y = a2 * x + b2
-    //
-    // a1 * x + b1 = a2 * x + b2
-    // (a1 - a2) * x = (b2 - b1)
-    // x = (b2 - b1) / (a1 - a2)
+fn main() -> anyhow::Result<()> {
+    let mut area = TestArea::default();
+    let mut pairs = false;
+    let mut configured = false;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(anyhow!("Missing value for {arg}"));
+        match arg.as_str() {
+            "--area" => area.bounds = range(&arg, &value()?)?,
+            "--axes" => area.axes = value()?.parse()?,
+            "--window" => area.window = Some(range(&arg, &value()?)?),
+            "--pairs" => pairs = true,
+            a => bail!("Unexpected argument {a:?}\n{USAGE}"),
+        }
+        configured = true;
+    }
+    if !configured {
+        return common::run_stdin::<day24::Day24>();
+    }
 
-    let trayectories = input
-        .iter()
-        .map(|([bx, by, ..], [ax, ay, ..])| {
-            let slope = *ay as f64 / *ax as f64;
-            let b = *by as f64 - slope * *bx as f64;
-            (slope, b)
-        })
-        .collect_vec();
+    let mut text = String::new();
+    std::io::stdin().lock().read_to_string(&mut text)?;
+    let hail = day24::Day24::parse(&text).map_err(|e| e.locate(&text))?;
 
-    let mut count = 0;
-    for (i, (a1, b1)) in trayectories.iter().enumerate() {
-        for (j, (a2, b2)) in trayectories.iter().enumerate().skip(i + 1) {
-            println!("intersection between {i} and {j}:");
-            if a1 == a2 {
-                println!("\t lines are parallel");
-            } else {
-                let x = (b2 - b1) / (a1 - a2);
-                let y = a1 * x + b1;
-                println!("\tx: {x}, y: {y}");
-                if time_for_point((x, y), &input[i]) < 0.0 {
-                    println!("\tbut it is in the past for {i}!");
-                    continue;
-                }
-                if time_for_point((x, y), &input[j]) < 0.0 {
-                    println!("\tbut it is in the past for {j}!");
-                    continue;
-                }
-                if x < TEST_AREA.0 || x > TEST_AREA.1 || y < TEST_AREA.0 || y > TEST_AREA.1 {
-                    println!("\tbut it is outside test area!");
-                    continue;
-                }
-                count += 1;
-            }
+    let reports = day24::reports(&hail, &area);
+    if pairs {
+        for (i, j, report) in &reports {
+            println!("{i} and {j}: {}", report.describe(area.axes));
         }
     }
-    println!("count {count}");
+    let inside = reports
+        .iter()
+        .filter(|(_, _, r)| matches!(r, Report::Inside(_)))
+        .count();
+    println!("{inside} crossings inside the test area");
+    Ok(())
 }
@@ -0,0 +1,91 @@
+use common::parse;
+use common::{Answer, ParseError, Solution};
+
+pub struct Game {
+    my_numbers: Vec<usize>,
+    winning_numbers: Vec<usize>,
+}
+
+fn parse_numbers(seq: &str) -> Result<Vec<usize>, ParseError> {
+    seq.split_ascii_whitespace().map(parse::number).collect()
+}
+
+fn parse(lines: &[&str]) -> Result<Vec<Game>, ParseError> {
+    lines
+        .iter()
+        .map(|line| {
+            let (_card, numbers) = parse::split_once(line, ":")?;
+            let (winning_seq, my_seq) = parse::split_once(numbers, "|")?;
+            Ok(Game {
+                my_numbers: parse_numbers(my_seq)?,
+                winning_numbers: parse_numbers(winning_seq)?,
+            })
+        })
+        .collect()
+}
+
+impl Game {
+    fn matches(&self) -> usize {
+        self.my_numbers
+            .iter()
+            .filter(|n| self.winning_numbers.contains(n))
+            .count()
+    }
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Vec<Game>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let lines: Vec<_> = input.lines().collect();
+        parse(&lines)
+    }
+
+    fn part1(games: &Self::Input) -> Answer {
+        games
+            .iter()
+            .map(|g| match g.matches() {
+                0 => 0,
+                count => 1 << (count - 1),
+            })
+            .sum::<usize>()
+            .into()
+    }
+
+    fn part2(games: &Self::Input) -> Answer {
+        let mut copies = vec![1; games.len()];
+        for (idx, g) in games.iter().enumerate() {
+            let count = g.matches();
+
+            let copies_of_current_card = copies[idx];
+            copies
+                .iter_mut()
+                .skip(idx + 1)
+                .take(count)
+                .for_each(|v| *v += copies_of_current_card);
+        }
+        copies.iter().sum::<usize>().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+";
+
+    #[test]
+    fn solves_example() {
+        let input = Day4::parse(EXAMPLE).unwrap();
+        assert_eq!(Day4::part1(&input), Answer::from(13_usize));
+        assert_eq!(Day4::part2(&input), Answer::from(30_usize));
+    }
+}
@@ -0,0 +1,340 @@
+use std::collections::HashMap;
+use std::ops::Range;
+
+use common::parse;
+use common::{Answer, IntervalMap, IntervalSet, ParseError, Solution};
+
+#[derive(Clone)]
+pub struct Map {
+    from: String,
+    to: String,
+    t: IntervalMap<usize>,
+}
+
+/// Every map of the almanac, indexed by the category they translate from.
+pub struct Maps {
+    maps: Vec<Map>,
+    graph: HashMap<String, usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChainError {
+    /// No map leaves `stuck`, which was reached on the way from `from` without finding `to`
+    Unreachable {
+        from: String,
+        to: String,
+        stuck: String,
+    },
+    /// The maps lead back to an already visited category
+    Cycle(Vec<String>),
+}
+
+impl std::fmt::Display for ChainError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChainError::Unreachable { from, to, stuck } => {
+                write!(f, "{to} cannot be reached from {from}: no map from {stuck}")
+            }
+            ChainError::Cycle(categories) => {
+                write!(f, "maps loop through {}", categories.join(" -> "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for ChainError {}
+
+impl Maps {
+    /// Maps to apply, in order, to go from one category to another.
+    pub fn chain(&self, from: &str, to: &str) -> Result<Vec<&Map>, ChainError> {
+        let mut chain = vec![];
+        let mut visited = vec![from];
+        let mut current = from;
+
+        while current != to {
+            let Some(map) = self.graph.get(current).map(|idx| &self.maps[*idx]) else {
+                return Err(ChainError::Unreachable {
+                    from: from.to_owned(),
+                    to: to.to_owned(),
+                    stuck: current.to_owned(),
+                });
+            };
+
+            if let Some(idx) = visited.iter().position(|c| *c == map.to) {
+                let mut cycle: Vec<_> = visited[idx..].iter().map(|c| c.to_string()).collect();
+                cycle.push(map.to.clone());
+                return Err(ChainError::Cycle(cycle));
+            }
+
+            visited.push(&map.to);
+            chain.push(map);
+            current = &map.to;
+        }
+        Ok(chain)
+    }
+
+    /// Single translation from one category to another.
+    pub fn mapping(&self, from: &str, to: &str) -> Result<IntervalMap<usize>, ChainError> {
+        Ok(self
+            .chain(from, to)?
+            .iter()
+            .fold(IntervalMap::new(), |composed, m| composed.then(&m.t)))
+    }
+}
+
+// Seeds are given as (start, length) pairs
+fn seed_ranges(seeds: &[usize]) -> Result<IntervalSet<usize>, String> {
+    if seeds.len() % 2 != 0 {
+        return Err(format!(
+            "{} seed numbers don't make (start, length) pairs",
+            seeds.len()
+        ));
+    }
+    seeds
+        .chunks_exact(2)
+        .map(|pair| match pair[0].checked_add(pair[1]) {
+            Some(end) => Ok(pair[0]..end),
+            None => Err(format!("{} seeds from {} are too many", pair[1], pair[0])),
+        })
+        .collect()
+}
+
+// Every seed on its own, as single-value ranges
+fn seed_points(seeds: &[usize]) -> Result<IntervalSet<usize>, String> {
+    seeds
+        .iter()
+        .map(|seed| match seed.checked_add(1) {
+            Some(end) => Ok(*seed..end),
+            None => Err(format!("seed {seed} is too large")),
+        })
+        .collect()
+}
+
+fn lowest_location(seeds: &IntervalSet<usize>, maps: &Maps) -> Result<usize, String> {
+    let mapping = maps
+        .mapping("seed", "location")
+        .expect("Validated while parsing");
+    mapping
+        .map_set(seeds)
+        .min()
+        .ok_or_else(|| "there are no seeds to plant".to_owned())
+}
+
+fn parse_map_header(line: &str) -> Result<(String, String), ParseError> {
+    let name = parse::strip_suffix(line, " map:")?;
+    let (from, to) = parse::split_once(name, "-to-")?;
+    Ok((from.to_owned(), to.to_owned()))
+}
+
+fn parse_range(line: &str) -> Result<(Range<usize>, usize), ParseError> {
+    let mut components = line.split_whitespace();
+    let dst: usize = parse::number(parse::next(&mut components, line, "a destination start")?)?;
+    let src: usize = parse::number(parse::next(&mut components, line, "a source start")?)?;
+    let len_txt = parse::next(&mut components, line, "a range length")?;
+    let len = parse::number(len_txt)?;
+    match (src.checked_add(len), dst.checked_add(len)) {
+        (Some(end), Some(_)) => Ok((src..end, dst)),
+        _ => Err(ParseError::new(
+            len_txt,
+            "a range length that fits in usize",
+        )),
+    }
+}
+
+/// Parses the seeds and maps without checking that seeds can be followed to a location.
+pub fn parse(input: &str) -> Result<(Vec<usize>, Maps), ParseError> {
+    let mut iter = input.lines();
+    let seeds_txt = parse::next(&mut iter, input, "a seeds line")?;
+    let seeds: Vec<usize> = parse::strip_prefix(seeds_txt, "seeds: ")?
+        .split_whitespace()
+        .map(parse::number)
+        .collect::<Result<_, _>>()?;
+
+    let mut maps = Maps {
+        maps: vec![],
+        graph: HashMap::new(),
+    };
+
+    while let Some(line) = iter.next() {
+        if line.is_empty() {
+            continue;
+        }
+
+        let (from, to) = parse_map_header(line)?;
+        if maps.graph.contains_key(&from) {
+            return Err(ParseError::new(line, format!("a single map from {from}")));
+        }
+
+        let mut map = Map {
+            from,
+            to,
+            t: IntervalMap::new(),
+        };
+
+        for line in iter.by_ref() {
+            if line.is_empty() {
+                break;
+            }
+
+            let (src, dst) = parse_range(line)?;
+            if !map.t.insert(src, dst) {
+                return Err(ParseError::new(
+                    line,
+                    "a source range not overlapping others",
+                ));
+            }
+        }
+        maps.graph.insert(map.from.clone(), maps.maps.len());
+        maps.maps.push(map);
+    }
+    Ok((seeds, maps))
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = (Vec<usize>, Maps);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let (seeds, maps) = parse(input)?;
+        if let Err(e) = maps.chain("seed", "location") {
+            return Err(ParseError::after(
+                input,
+                format!("maps from seed to location ({e})"),
+            ));
+        }
+        Ok((seeds, maps))
+    }
+
+    fn part1((seeds, maps): &Self::Input) -> Answer {
+        match seed_points(seeds).and_then(|seeds| lowest_location(&seeds, maps)) {
+            Ok(location) => location.into(),
+            Err(e) => e.into(),
+        }
+    }
+
+    fn part2((seeds, maps): &Self::Input) -> Answer {
+        match seed_ranges(seeds).and_then(|seeds| lowest_location(&seeds, maps)) {
+            Ok(location) => location.into(),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4
+";
+
+    #[test]
+    fn solves_example() {
+        let input = Day5::parse(EXAMPLE).unwrap();
+        assert_eq!(Day5::part1(&input), Answer::from(35_usize));
+        assert_eq!(Day5::part2(&input), Answer::from(46_usize));
+    }
+
+    #[test]
+    fn seeds_without_pairs() {
+        let odd = Day5::parse(&EXAMPLE.replace("79 14 55 13", "79 14 55")).unwrap();
+        assert_eq!(Day5::part1(&odd), Answer::from(43_usize));
+        assert_eq!(
+            Day5::part2(&odd),
+            Answer::from("3 seed numbers don't make (start, length) pairs".to_owned())
+        );
+        let empty = Day5::parse(&EXAMPLE.replace("79 14 55 13", "79 0")).unwrap();
+        assert_eq!(
+            Day5::part2(&empty),
+            Answer::from("there are no seeds to plant".to_owned())
+        );
+
+        let input = EXAMPLE.replace("52 50 48", &format!("52 50 {}", usize::MAX));
+        let err = Day5::parse(&input).err().unwrap().locate(&input);
+        assert_eq!(err.position(), Some((5, 7)));
+    }
+
+    const ALMANAC: &str = "seeds: 79 14
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-water map:
+0 15 37
+
+water-to-light map:
+88 18 7
+";
+
+    #[test]
+    fn resolves_chains() {
+        let (_, maps) = parse(ALMANAC).unwrap();
+        let categories = |chain: Vec<&Map>| chain.iter().map(|m| m.to.clone()).collect::<Vec<_>>();
+
+        assert_eq!(
+            categories(maps.chain("seed", "light").unwrap()),
+            ["soil", "water", "light"]
+        );
+        assert!(maps.chain("soil", "soil").unwrap().is_empty());
+        assert_eq!(maps.mapping("soil", "water").unwrap().get(20), 5);
+        assert_eq!(maps.mapping("seed", "light").unwrap().get(79), 81);
+
+        assert_eq!(
+            maps.chain("seed", "location").err(),
+            Some(ChainError::Unreachable {
+                from: "seed".into(),
+                to: "location".into(),
+                stuck: "light".into(),
+            })
+        );
+        assert!(Day5::parse(ALMANAC).is_err());
+    }
+
+    #[test]
+    fn detects_cycles() {
+        let input = format!("{ALMANAC}\nlight-to-soil map:\n0 0 1\n");
+        let (_, maps) = parse(&input).unwrap();
+        assert_eq!(
+            maps.chain("seed", "location").err(),
+            Some(ChainError::Cycle(vec![
+                "soil".into(),
+                "water".into(),
+                "light".into(),
+                "soil".into()
+            ]))
+        );
+    }
+}
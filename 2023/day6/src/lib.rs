@@ -0,0 +1,92 @@
+use common::parse;
+use common::{Answer, ParseError, Solution};
+
+pub struct Race {
+    time: usize,
+    distance: usize,
+}
+
+pub struct Races {
+    races: Vec<Race>,
+    kerned: Race,
+}
+
+fn parse_numbers(line: &str) -> Result<Vec<usize>, ParseError> {
+    line.split_whitespace().map(parse::number).collect()
+}
+
+fn parse_kerned(line: &str) -> Result<usize, ParseError> {
+    // Numbers are validated one by one before joining them
+    parse_numbers(line)?;
+    line.split_whitespace()
+        .collect::<String>()
+        .parse::<usize>()
+        .map_err(|_| ParseError::new(line.trim(), "a number that fits in 64 bits"))
+}
+
+fn num_solutions(race: &Race) -> usize {
+    let t = race.time as f64;
+    let d = (race.distance + 1) as f64;
+    let min_time = (t - f64::sqrt(t * t - 4.0 * d)) / 2.0;
+    let max_time = (t + f64::sqrt(t * t - 4.0 * d)) / 2.0;
+    let min_time = min_time.ceil() as usize;
+    let max_time = max_time.floor() as usize;
+
+    max_time - min_time + 1
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Races;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut iter = input.lines();
+
+        let time = parse::strip_prefix(parse::next(&mut iter, input, "a Time line")?, "Time:")?;
+        let distance = parse::strip_prefix(
+            parse::next(&mut iter, input, "a Distance line")?,
+            "Distance:",
+        )?;
+
+        let races = parse_numbers(time)?
+            .into_iter()
+            .zip(parse_numbers(distance)?)
+            .map(|(time, distance)| Race { time, distance })
+            .collect();
+        let kerned = Race {
+            time: parse_kerned(time)?,
+            distance: parse_kerned(distance)?,
+        };
+        Ok(Races { races, kerned })
+    }
+
+    fn part1(races: &Self::Input) -> Answer {
+        races
+            .races
+            .iter()
+            .map(num_solutions)
+            .product::<usize>()
+            .into()
+    }
+
+    fn part2(races: &Self::Input) -> Answer {
+        num_solutions(&races.kerned).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Time:      7  15   30
+Distance:  9  40  200
+";
+
+    #[test]
+    fn solves_example() {
+        let input = Day6::parse(EXAMPLE).unwrap();
+        assert_eq!(Day6::part1(&input), Answer::from(288_usize));
+        assert_eq!(Day6::part2(&input), Answer::from(71503_usize));
+    }
+}
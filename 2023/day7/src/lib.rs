@@ -0,0 +1,174 @@
+use std::collections::HashMap;
+
+use common::parse;
+use common::{Answer, ParseError, Solution};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Rules {
+    Jacks,
+    Jokers,
+}
+
+impl Rules {
+    fn card_order(&self) -> &'static str {
+        match self {
+            Rules::Jacks => "23456789TJQKA",
+            Rules::Jokers => "J23456789TQKA",
+        }
+    }
+
+    fn rank(&self, card: char) -> Option<usize> {
+        self.card_order().find(card)
+    }
+}
+
+// Variants are declared from weakest to strongest so that the derived ordering compares the hand
+// type first and then the card ranks in order.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Hand {
+    HighCard(Vec<usize>),
+    OnePair(Vec<usize>),
+    TwoPair(Vec<usize>),
+    ThreeOfAKind(Vec<usize>),
+    FullHouse(Vec<usize>),
+    FourOfAKind(Vec<usize>),
+    FiveOfAKind(Vec<usize>),
+}
+
+impl Hand {
+    fn parse(value: &str, rules: Rules) -> Result<Self, ParseError> {
+        if value.len() != 5 {
+            return Err(ParseError::new(value, "a hand of 5 cards"));
+        }
+
+        let ranks = value
+            .char_indices()
+            .map(|(i, c)| {
+                rules
+                    .rank(c)
+                    .ok_or_else(|| ParseError::new(&value[i..i + c.len_utf8()], "a card"))
+            })
+            .collect::<Result<Vec<usize>, ParseError>>()?;
+
+        let map = value.chars().fold(HashMap::new(), |mut m, c| {
+            let n = m.get_key_value(&c).map(|(_, n)| *n).unwrap_or(0);
+            m.insert(c, n + 1);
+            m
+        });
+
+        let num_jokers = match rules {
+            Rules::Jacks => 0,
+            Rules::Jokers => map
+                .iter()
+                .find(|(k, _)| **k == 'J')
+                .map(|(_, v)| *v)
+                .unwrap_or(0),
+        };
+
+        // if only jokers are present, return five of kind
+        if num_jokers == 5 {
+            return Ok(Self::FiveOfAKind(ranks));
+        }
+
+        // Max non-joker count
+        let max_count = map
+            .iter()
+            .map(|(k, v)| if *k == 'J' && num_jokers != 0 { 0 } else { *v })
+            .max()
+            .unwrap();
+
+        assert_ne!(max_count, 0);
+
+        let compensated_len = if num_jokers != 0 {
+            map.len() - 1
+        } else {
+            map.len()
+        };
+
+        Ok(match max_count + num_jokers {
+            5 => Self::FiveOfAKind(ranks),
+            4 => Self::FourOfAKind(ranks),
+            3 if (compensated_len == 2) => Self::FullHouse(ranks),
+            3 => Self::ThreeOfAKind(ranks),
+            2 if (compensated_len == 3) => Self::TwoPair(ranks),
+            2 => Self::OnePair(ranks),
+            1 => Self::HighCard(ranks),
+            _ => {
+                return Err(ParseError::new(value, "a valid hand"));
+            }
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct Bet {
+    cards: String,
+    bet: usize,
+}
+
+fn total_winnings(bets: &[Bet], rules: Rules) -> Result<usize, ParseError> {
+    let mut hands = bets
+        .iter()
+        .map(|b| Ok((Hand::parse(&b.cards, rules)?, b.bet)))
+        .collect::<Result<Vec<(Hand, usize)>, ParseError>>()?;
+    hands.sort_by(|(h1, _), (h2, _)| h1.cmp(h2));
+
+    Ok(hands
+        .iter()
+        .enumerate()
+        .fold(0, |acc, (idx, (_, bet))| acc + (idx + 1) * bet))
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Vec<Bet>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input
+            .lines()
+            .map(|l| {
+                let mut iter = l.split_whitespace();
+                let cards = parse::next(&mut iter, l, "a hand")?;
+                // Validate the hand up front so that both parts can rely on it
+                Hand::parse(cards, Rules::Jokers)?;
+                let bet = parse::number(parse::next(&mut iter, l, "a bet")?)?;
+                Ok(Bet {
+                    cards: cards.to_string(),
+                    bet,
+                })
+            })
+            .collect()
+    }
+
+    fn part1(bets: &Self::Input) -> Answer {
+        total_winnings(bets, Rules::Jacks)
+            .expect("Hands are validated while parsing")
+            .into()
+    }
+
+    fn part2(bets: &Self::Input) -> Answer {
+        total_winnings(bets, Rules::Jokers)
+            .expect("Hands are validated while parsing")
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483
+";
+
+    #[test]
+    fn solves_example() {
+        let input = Day7::parse(EXAMPLE).unwrap();
+        assert_eq!(Day7::part1(&input), Answer::from(6440_usize));
+        assert_eq!(Day7::part2(&input), Answer::from(5905_usize));
+    }
+}
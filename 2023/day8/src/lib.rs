@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+
+use common::parse;
+use common::{Answer, ParseError, Solution};
+
+fn gcd(mut a: usize, mut b: usize) -> usize {
+    if a == b {
+        return a;
+    }
+
+    if b > a {
+        std::mem::swap(&mut a, &mut b);
+    }
+
+    while b != 0 {
+        let temp = a;
+        a = b;
+        b = temp % b;
+    }
+
+    a
+}
+
+fn lcm(a: usize, b: usize) -> usize {
+    a * b / gcd(a, b)
+}
+
+pub struct Network {
+    instructions: String,
+    map: HashMap<String, (String, String)>,
+}
+
+impl Network {
+    fn step<'a>(&'a self, cur: &str, inst: char) -> &'a str {
+        match inst {
+            'L' => &self.map[cur].0,
+            'R' => &self.map[cur].1,
+            inst => {
+                panic!("Unexpected instruction: {inst:?}");
+            }
+        }
+    }
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Network;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut line_iter = input.lines();
+
+        let instructions = parse::next(&mut line_iter, input, "an instructions line")?;
+        parse::check_chars(instructions, "LR")?;
+
+        let empty_line = parse::next(&mut line_iter, instructions, "an empty line")?;
+        if !empty_line.is_empty() {
+            return Err(ParseError::new(empty_line, "an empty line"));
+        }
+
+        let mut map = HashMap::new();
+        let mut references = vec![];
+        for l in line_iter {
+            let (parent, leafs) = parse::split_once(l, "=")?;
+            let leafs = leafs.trim();
+            let leafs = parse::strip_prefix(leafs, "(")?;
+            let leafs = parse::strip_suffix(leafs, ")")?;
+            let (left, right) = parse::split_once(leafs, ",")?;
+            references.push(left.trim());
+            references.push(right.trim());
+
+            map.insert(
+                parent.trim().to_string(),
+                (left.trim().to_string(), right.trim().to_string()),
+            );
+        }
+
+        if let Some(undefined) = references.iter().find(|r| !map.contains_key(**r)) {
+            return Err(ParseError::new(undefined, "a defined node"));
+        }
+
+        Ok(Network {
+            instructions: instructions.to_string(),
+            map,
+        })
+    }
+
+    fn part1(network: &Self::Input) -> Answer {
+        if let Some(missing) = ["AAA", "ZZZ"]
+            .iter()
+            .find(|n| !network.map.contains_key(**n))
+        {
+            return format!("no node {missing} in the network").into();
+        }
+        // Past this many steps some node was reached twice at the same instruction, so the walk
+        // only goes round in circles
+        let limit = network.map.len() * network.instructions.len();
+
+        let mut cur = "AAA";
+        let mut count = 0usize;
+        for inst in network.instructions.chars().cycle() {
+            if cur == "ZZZ" {
+                break;
+            }
+            if count == limit {
+                return "ZZZ cannot be reached from AAA".to_string().into();
+            }
+            cur = network.step(cur, inst);
+            count += 1;
+        }
+        count.into()
+    }
+
+    fn part2(network: &Self::Input) -> Answer {
+        let Network { instructions, map } = network;
+        let current_nodes: Vec<_> = map
+            .keys()
+            .filter_map(|k| {
+                if k.ends_with('A') {
+                    Some(k.as_str())
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        let cycles: Vec<_> = current_nodes
+            .iter()
+            .map(|n| {
+                let mut cur = *n;
+                let mut instruction_iter = instructions.chars().cycle();
+                let mut count = 0;
+                while !cur.ends_with('Z') {
+                    let inst = instruction_iter.next().expect("Should never end");
+                    cur = network.step(cur, inst);
+                    count += 1;
+                }
+                let first = count;
+                count = 0;
+
+                while count == 0 || !cur.ends_with('Z') {
+                    let inst = instruction_iter.next().expect("Should never end");
+                    cur = network.step(cur, inst);
+                    count += 1;
+                }
+                (first, count)
+            })
+            .collect();
+
+        assert!(cycles.iter().all(|(a, b)| a == b));
+        assert!(cycles.iter().all(|(a, _)| a % instructions.len() == 0));
+
+        cycles.iter().fold(1, |v, (a, _)| lcm(v, *a)).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)
+";
+
+    #[test]
+    fn walks_to_zzz() {
+        assert_eq!(
+            Day8::part1(&Day8::parse(EXAMPLE).unwrap()),
+            Answer::from(6_usize)
+        );
+
+        let circles = Day8::parse(&EXAMPLE.replace("(AAA, ZZZ)", "(AAA, AAA)")).unwrap();
+        assert_eq!(
+            Day8::part1(&circles),
+            Answer::from("ZZZ cannot be reached from AAA".to_string())
+        );
+        let no_start = Day8::parse("LR\n\n11A = (11Z, 11Z)\n11Z = (11A, 11A)\n").unwrap();
+        assert_eq!(
+            Day8::part1(&no_start),
+            Answer::from("no node AAA in the network".to_string())
+        );
+    }
+}
@@ -0,0 +1,89 @@
+use common::parse;
+use common::{Answer, ParseError, Solution};
+
+fn differences(history: &[isize]) -> Vec<Vec<isize>> {
+    let mut diffs = vec![history.to_vec()];
+
+    while !diffs
+        .last()
+        .expect("At least one element")
+        .iter()
+        .all(|v| *v == 0)
+    {
+        let diff = diffs
+            .last()
+            .expect("At least one element")
+            .iter()
+            .fold((vec![], None), |(mut v, p), elem| match p {
+                None => (v, Some(elem)),
+                Some(p) => {
+                    v.push(elem - p);
+                    (v, Some(elem))
+                }
+            })
+            .0;
+
+        diffs.push(diff);
+    }
+
+    diffs
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Vec<Vec<isize>>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input
+            .lines()
+            .map(|line| line.split_whitespace().map(parse::number).collect())
+            .collect()
+    }
+
+    fn part1(histories: &Self::Input) -> Answer {
+        histories
+            .iter()
+            .map(|history| {
+                differences(history)
+                    .iter()
+                    .map(|diff| diff.last().expect("At least one value"))
+                    .sum::<isize>()
+            })
+            .sum::<isize>()
+            .into()
+    }
+
+    fn part2(histories: &Self::Input) -> Answer {
+        histories
+            .iter()
+            .map(|history| {
+                let diffs = differences(history);
+
+                let mut decr = 0;
+                for diff in diffs.iter().rev().skip(1) {
+                    decr = diff.first().expect("At least one value") - decr;
+                }
+                decr
+            })
+            .sum::<isize>()
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45
+";
+
+    #[test]
+    fn solves_example() {
+        let input = Day9::parse(EXAMPLE).unwrap();
+        assert_eq!(Day9::part1(&input), Answer::from(114_i64));
+        assert_eq!(Day9::part2(&input), Answer::from(2_i64));
+    }
+}
@@ -1,21 +1,27 @@
+use common::parse;
+use common::ParseError;
 use good_lp::{default_solver, variable, Expression, ProblemVariables, Solution, SolverModel};
 
 #[derive(Debug)]
 struct Button(u64, Vec<usize>);
 
 impl Button {
-    fn parse(data: &str) -> Self {
-        let iter = data
-            .trim_matches(['(', ')'])
+    fn parse(data: &str) -> Result<Self, ParseError> {
+        let data = parse::strip_prefix(data, "(")?;
+        let data = parse::strip_suffix(data, ")")?;
+        let indices: Vec<usize> = data
             .split(',')
-            .map(|idx| idx.parse::<usize>().unwrap());
+            .map(|idx| match parse::number(idx)? {
+                idx @ 0..64 => Ok(idx),
+                _ => Err(ParseError::new(idx, "a light index below 64")),
+            })
+            .collect::<Result<_, _>>()?;
 
-        let mask = iter.clone().fold(0, |result, idx| {
+        let mask = indices.iter().fold(0, |result, idx| {
             let mask = 1 << idx;
             mask | result
         });
-        let indices = iter.collect();
-        Self(mask, indices)
+        Ok(Self(mask, indices))
     }
 
     fn mask(&self) -> u64 {
@@ -27,15 +33,21 @@ impl Button {
 struct Leds(u64);
 
 impl Leds {
-    fn parse(data: &str) -> Self {
-        let data = data.trim_matches(['[', ']']);
-        Self(data.chars().enumerate().fold(0, |result, (idx, v)| {
+    fn parse(data: &str) -> Result<Self, ParseError> {
+        let data = parse::strip_prefix(data, "[")?;
+        let data = parse::strip_suffix(data, "]")?;
+        parse::check_chars(data, ".#")?;
+        if data.len() > 64 {
+            return Err(ParseError::new(data, "at most 64 lights"));
+        }
+        let mask = data.chars().enumerate().fold(0, |result, (idx, v)| {
             if v == '#' {
                 result | (1 << idx)
             } else {
                 result
             }
-        }))
+        });
+        Ok(Self(mask))
     }
 
     fn mask(&self) -> u64 {
@@ -47,14 +59,15 @@ impl Leds {
 struct Joltage(Vec<usize>);
 
 impl Joltage {
-    fn parse(data: &str) -> Self {
+    fn parse(data: &str) -> Result<Self, ParseError> {
+        let data = parse::strip_prefix(data, "{")?;
+        let data = parse::strip_suffix(data, "}")?;
         let joltage = data
-            .trim_matches(['{', '}'])
             .split(',')
-            .map(|idx| idx.parse::<usize>().unwrap())
-            .collect();
+            .map(parse::number)
+            .collect::<Result<_, _>>()?;
 
-        Self(joltage)
+        Ok(Self(joltage))
     }
 }
 
@@ -65,6 +78,35 @@ struct Machine {
     req_joltage: Joltage,
 }
 
+impl Machine {
+    fn parse(line: &str) -> Result<Self, ParseError> {
+        let mut parts = line.split_whitespace();
+        let leds = Leds::parse(parse::next(&mut parts, line, "a light diagram")?)?;
+        let mut buttons = vec![];
+
+        let mut req_joltage = None;
+        for part in parts.by_ref() {
+            if part.starts_with("{") {
+                req_joltage = Some(Joltage::parse(part)?);
+                break;
+            }
+
+            let btn = Button::parse(part)?;
+            buttons.push(btn);
+        }
+
+        if let Some(extra) = parts.next() {
+            return Err(ParseError::new(extra, "the end of the machine"));
+        }
+        Ok(Machine {
+            leds,
+            buttons,
+            req_joltage: req_joltage
+                .ok_or_else(|| ParseError::after(line, "joltage requirements"))?,
+        })
+    }
+}
+
 fn min_presses_impl(m: &Machine, cur: u64, btns: &[Button]) -> Option<usize> {
     btns.iter()
         .enumerate()
@@ -165,31 +207,14 @@ fn min_joltage_but_fast(m: &Machine) -> usize {
 
 fn main() {
     let filename = std::env::args().nth(1).unwrap();
-    let data: Vec<Machine> = std::fs::read_to_string(filename)
-        .unwrap()
-        .lines()
-        .map(|l| {
-            let mut parts = l.split_whitespace();
-            let leds = Leds::parse(parts.next().unwrap());
-            let mut buttons = vec![];
-
-            let mut req_joltage = None;
-            for part in parts {
-                if part.starts_with("{") {
-                    req_joltage = Some(Joltage::parse(part));
-                    break;
-                }
-
-                let btn = Button::parse(part);
-                buttons.push(btn);
-            }
-            Machine {
-                leds,
-                buttons,
-                req_joltage: req_joltage.unwrap(),
-            }
-        })
-        .collect();
+    let text = std::fs::read_to_string(&filename).unwrap();
+    let data: Vec<Machine> = match text.lines().map(Machine::parse).collect() {
+        Ok(data) => data,
+        Err(e) => {
+            eprintln!("{}", e.locate(&text).with_file(&filename));
+            std::process::exit(1);
+        }
+    };
 
     let result: usize = data.iter().map(min_presses).sum();
     println!("p1: {result}");
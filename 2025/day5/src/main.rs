@@ -1,33 +1,15 @@
-fn is_fresh(id: usize, ranges: &[(usize, usize)]) -> bool {
-    ranges.iter().any(|(begin, end)| id >= *begin && id <= *end)
-}
+use common::IntervalSet;
 
-fn join_ranges(left: (usize, usize), right: (usize, usize)) -> Option<(usize, usize)> {
-    if left.1 >= right.0 && right.1 >= left.0 {
-        let begin = left.0.min(right.0);
-        let end = left.1.max(right.1);
-        Some((begin, end))
-    } else {
-        None
+// Input ranges are inclusive on both ends
+fn fresh_ranges(ranges: &[(usize, usize)]) -> IntervalSet<usize> {
+    let mut fresh = IntervalSet::new();
+    for (begin, end) in ranges {
+        let end = end
+            .checked_add(1)
+            .expect("Fresh id range ends before usize::MAX");
+        fresh.insert(*begin..end);
     }
-}
-
-fn deduplicate_ranges(ranges: &[(usize, usize)]) -> Vec<(usize, usize)> {
-    let mut new_ranges = vec![];
-
-    for range in ranges {
-        if let Some((id, new)) = new_ranges
-            .iter_mut()
-            .enumerate()
-            .find_map(|(id, new)| join_ranges(*new, *range).map(|v| (id, v)))
-        {
-            new_ranges[id] = new;
-        } else {
-            new_ranges.push(*range);
-        }
-    }
-
-    new_ranges
+    fresh
 }
 
 fn main() {
@@ -36,7 +18,7 @@ fn main() {
 
     let data = std::fs::read_to_string(filename).unwrap();
 
-    let mut ranges: Vec<_> = data
+    let ranges: Vec<_> = data
         .lines()
         .take_while(|l| !l.is_empty())
         .map(|l| {
@@ -58,22 +40,11 @@ fn main() {
         .map(|l| l.parse().unwrap())
         .collect();
 
-    let val = available.iter().fold(0, |acc, id| {
-        acc + if is_fresh(*id, &ranges) { 1 } else { 0 }
-    });
+    let fresh = fresh_ranges(&ranges);
+    let val = available.iter().filter(|id| fresh.contains(**id)).count();
     println!("count: {val}");
 
-    loop {
-        let new_ranges = deduplicate_ranges(&ranges);
-        if new_ranges.len() == ranges.len() {
-            break;
-        }
-        ranges = new_ranges;
-    }
-
-    let result = ranges
-        .iter()
-        .fold(0, |acc, (begin, end)| acc + (end - begin + 1));
+    let result = fresh.len();
     println!("fresh_ids: {result}");
 }
 
@@ -82,14 +53,28 @@ mod test {
     use super::*;
 
     #[test]
-    fn join_ranges_test() {
-        assert_eq!(join_ranges((0, 12), (12, 23)), Some((0, 23)));
-        assert_eq!(join_ranges((0, 13), (12, 23)), Some((0, 23)));
-        assert_eq!(join_ranges((0, 24), (12, 23)), Some((0, 24)));
-        assert_eq!(join_ranges((11, 24), (12, 23)), Some((11, 24)));
-        assert_eq!(join_ranges((12, 24), (12, 23)), Some((12, 24)));
-        assert_eq!(join_ranges((13, 24), (12, 23)), Some((12, 24)));
-        assert_eq!(join_ranges((0, 11), (12, 23)), None);
-        assert_eq!(join_ranges((12, 23), (0, 11)), None);
+    fn fresh_ranges_test() {
+        let fresh = fresh_ranges(&[(10, 14), (16, 20), (12, 18), (3, 5)]);
+        assert_eq!(fresh.ranges(), &[3..6, 10..21]);
+        assert_eq!(fresh.len(), 14);
+        assert!(fresh.contains(5) && !fresh.contains(8));
+        // Overlapping, nested and adjacent ranges end up in a single range of the set
+        for (left, right) in [
+            (0, 12),
+            (0, 13),
+            (0, 24),
+            (11, 24),
+            (12, 24),
+            (13, 24),
+            (0, 11),
+        ] {
+            let joined = IntervalSet::from(left.min(12)..right.max(23) + 1);
+            assert_eq!(fresh_ranges(&[(left, right), (12, 23)]), joined);
+            assert_eq!(fresh_ranges(&[(12, 23), (left, right)]), joined);
+        }
+        assert_eq!(
+            fresh_ranges(&[(0, 10), (12, 23)]).ranges(),
+            &[0..11, 12..24]
+        );
     }
 }
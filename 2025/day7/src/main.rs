@@ -1,12 +1,14 @@
 use std::collections::HashMap;
 
+use common::Grid;
+
 fn simulate_and_get_splits(
-    map: &[Vec<char>],
+    map: &Grid<char>,
     (row, col): (isize, isize),
     solutions: &mut HashMap<(isize, isize), usize>,
 ) -> usize {
-    let width = map[0].len() as isize;
-    let height = map.len() as isize;
+    let width = map.cols() as isize;
+    let height = map.rows() as isize;
 
     if row >= height {
         return 1;
@@ -20,7 +22,7 @@ fn simulate_and_get_splits(
         return *sol;
     }
 
-    let result = if map[row as usize][col as usize] == '^' {
+    let result = if map[(row as usize, col as usize)] == '^' {
         let left = simulate_and_get_splits(map, (row, col - 1), solutions);
         let right = simulate_and_get_splits(map, (row, col + 1), solutions);
         left + right
@@ -34,21 +36,17 @@ fn simulate_and_get_splits(
 
 fn main() {
     let filename = std::env::args().nth(1).unwrap();
-    let data: Vec<Vec<char>> = std::fs::read_to_string(filename)
-        .unwrap()
-        .lines()
-        .map(|l| l.chars().collect())
-        .collect();
-
-    let start = data
-        .iter()
-        .enumerate()
-        .find_map(|(row, data)| {
-            data.iter()
-                .enumerate()
-                .find_map(|(col, elem)| (*elem == 'S').then_some((row as isize, col as isize)))
-        })
-        .unwrap();
+    let text = std::fs::read_to_string(&filename).unwrap();
+    let data = match Grid::parse(&text, ".S^") {
+        Ok(data) => data,
+        Err(e) => {
+            eprintln!("{}", e.locate(&text).with_file(&filename));
+            std::process::exit(1);
+        }
+    };
+
+    let (row, col) = data.find(&'S').unwrap();
+    let start = (row as isize, col as isize);
 
     let mut solutions = HashMap::new();
     let timelines = simulate_and_get_splits(&data, start, &mut solutions);